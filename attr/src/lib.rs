@@ -0,0 +1,250 @@
+// Copyright (c) 2014-2017 Guillaume Pinot <texitoi(a)texitoi.eu>
+//
+// This work is free. You can redistribute it and/or modify it under
+// the terms of the Do What The Fuck You Want To Public License,
+// Version 2, as published by Sam Hocevar. See the COPYING file for
+// more details.
+
+//! An attribute alternative to the `pub_iterator_type!` macro of the
+//! `pub-iterator-type` crate.
+//!
+//! `#[pub_iterator]` can be put on a tuple struct wrapping the hidden
+//! iterator, or on a type alias of the hidden iterator. The item is
+//! then replaced by the corresponding `pub_iterator_type!`
+//! declaration, so the generated code is the same. The crate using
+//! the attribute must thus also depend on `pub-iterator-type` (and
+//! declare it with `extern crate pub_iterator_type;` at its root in
+//! the 2015 edition).
+//!
+//! ```
+//! use pub_iterator_type_attr::pub_iterator;
+//!
+//! /// An iterator over the lines of a string.
+//! #[pub_iterator]
+//! pub struct Lines<'a>(std::str::Lines<'a>);
+//!
+//! /// An iterator over the elements of a slice.
+//! #[pub_iterator(double_ended, exact_size)]
+//! pub type Iter<'a, T> = std::slice::Iter<'a, T>;
+//!
+//! # fn main() {
+//! assert_eq!(vec!["a", "b"], Lines("a\nb".lines()).collect::<Vec<_>>());
+//! let iter = Iter([1, 2, 3].iter());
+//! assert_eq!(3, iter.len());
+//! assert_eq!(vec![&3, &2, &1], iter.rev().collect::<Vec<_>>());
+//! # }
+//! ```
+//!
+//! The attribute arguments are the forwarded traits, the struct
+//! having at most a second `PhantomData` field:
+//!
+//! - `double_ended`: the `DoubleEndedIterator` marker;
+//! - `exact_size`: the `ExactSizeIterator` marker;
+//! - `exact_size_hint`: the `ExactSizeHint` marker;
+//! - `fused`: the `FusedIterator` marker;
+//! - `trusted_len`: the `TrustedLen` marker;
+//! - `stream`: the `Stream` marker;
+//! - `parallel`: the `ParallelIterator` marker;
+//! - `min_max`: the `MinMax` marker;
+//! - `try_fold`: the `TryFold` marker;
+//! - `advance_by`: the `AdvanceBy` marker;
+//! - `is_empty`: the `IsEmpty` marker;
+//! - `from`: the `From` marker;
+//! - `send` and `sync`: the `Send` and `Sync` markers;
+//! - `debug`: the `Debug` marker.
+//!
+//! See the documentation of `pub_iterator_type!` for what each
+//! marker does.
+//!
+//! ```compile_fail
+//! use pub_iterator_type_attr::pub_iterator;
+//!
+//! /// Not a known forward.
+//! #[pub_iterator(backward)]
+//! pub struct Numbers(std::ops::Range<u32>);
+//! # fn main() {}
+//! ```
+
+extern crate proc_macro;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{
+    Attribute, Fields, GenericArgument, GenericParam, Generics, Ident, Item, PathArguments, Token,
+    Type, Visibility,
+};
+
+/// Declares the annotated tuple struct or type alias with
+/// `pub_iterator_type!`.
+#[proc_macro_attribute]
+pub fn pub_iterator(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    expand(args.into(), input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+struct Decl {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    ident: Ident,
+    generics: Generics,
+    field_attrs: Vec<Attribute>,
+    inner: Type,
+    phantom: Option<Type>,
+}
+
+fn expand(args: TokenStream, input: TokenStream) -> syn::Result<TokenStream> {
+    let markers = Punctuated::<Ident, Token![,]>::parse_terminated
+        .parse2(args)?
+        .iter()
+        .map(marker)
+        .collect::<syn::Result<Vec<_>>>()?;
+    let decl = match syn::parse2(input)? {
+        Item::Struct(s) => {
+            let fields = match s.fields {
+                Fields::Unnamed(fields) => fields.unnamed,
+                fields => {
+                    return Err(syn::Error::new_spanned(
+                        fields,
+                        "expected a tuple struct wrapping the hidden iterator",
+                    ))
+                }
+            };
+            if fields.is_empty() || fields.len() > 2 {
+                return Err(syn::Error::new_spanned(
+                    fields,
+                    "expected the hidden iterator, and optionally a `PhantomData`",
+                ));
+            }
+            let mut fields = fields.into_iter();
+            let field = fields.next().unwrap();
+            let phantom = fields.next().map(|f| phantom(&f.ty)).transpose()?;
+            if !matches!(field.vis, Visibility::Inherited) {
+                return Err(syn::Error::new_spanned(
+                    field.vis,
+                    "the hidden iterator must be private",
+                ));
+            }
+            Decl {
+                attrs: s.attrs,
+                vis: s.vis,
+                ident: s.ident,
+                generics: s.generics,
+                field_attrs: field.attrs,
+                inner: field.ty,
+                phantom,
+            }
+        }
+        Item::Type(t) => Decl {
+            attrs: t.attrs,
+            vis: t.vis,
+            ident: t.ident,
+            generics: t.generics,
+            field_attrs: vec![],
+            inner: *t.ty,
+            phantom: None,
+        },
+        item => {
+            return Err(syn::Error::new_spanned(
+                item,
+                "expected a tuple struct or a type alias",
+            ))
+        }
+    };
+    if let Some(attr) = decl.generics.params.iter().flat_map(param_attrs).next() {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "attributes on generic parameters are not supported",
+        ));
+    }
+
+    let Decl {
+        attrs,
+        vis,
+        ident,
+        generics,
+        field_attrs,
+        inner,
+        phantom,
+    } = decl;
+    // Without visibility, the macro declares a `pub` struct.
+    let vis = match vis {
+        Visibility::Inherited => quote!(pub(self)),
+        vis => quote!(#vis),
+    };
+    let params = generics.params.iter();
+    let phantom = phantom.map(|ty| quote!(, PhantomData<#ty>));
+    let markers = if markers.is_empty() {
+        quote!()
+    } else {
+        quote!(: #(#markers)+*)
+    };
+    let where_clause = &generics.where_clause;
+    Ok(quote! {
+        ::pub_iterator_type::pub_iterator_type! {
+            #(#attrs)*
+            #vis #ident[#(#params),*] = #(#field_attrs)* #inner #phantom #markers #where_clause;
+        }
+    })
+}
+
+fn marker(arg: &Ident) -> syn::Result<Ident> {
+    let marker = match arg.to_string().as_str() {
+        "double_ended" => "DoubleEndedIterator",
+        "exact_size" => "ExactSizeIterator",
+        "exact_size_hint" => "ExactSizeHint",
+        "fused" => "FusedIterator",
+        "trusted_len" => "TrustedLen",
+        "stream" => "Stream",
+        "parallel" => "ParallelIterator",
+        "min_max" => "MinMax",
+        "try_fold" => "TryFold",
+        "advance_by" => "AdvanceBy",
+        "is_empty" => "IsEmpty",
+        "from" => "From",
+        "send" => "Send",
+        "sync" => "Sync",
+        "debug" => "Debug",
+        _ => {
+            return Err(syn::Error::new(
+                arg.span(),
+                format!("unknown forward `{}`", arg),
+            ))
+        }
+    };
+    Ok(Ident::new(marker, arg.span()))
+}
+
+/// Returns `T` from `PhantomData<T>`, whatever its path.
+fn phantom(ty: &Type) -> syn::Result<Type> {
+    if let Type::Path(path) = ty {
+        let last = path.path.segments.last().unwrap();
+        if let PathArguments::AngleBracketed(ref args) = last.arguments {
+            if let (true, 1, Some(GenericArgument::Type(arg))) = (
+                last.ident == "PhantomData",
+                args.args.len(),
+                args.args.first(),
+            ) {
+                return Ok(arg.clone());
+            }
+        }
+    }
+    Err(syn::Error::new(
+        ty.span(),
+        "expected a `PhantomData` as second field",
+    ))
+}
+
+fn param_attrs(param: &GenericParam) -> &[Attribute] {
+    match param {
+        GenericParam::Lifetime(p) => &p.attrs,
+        GenericParam::Type(p) => &p.attrs,
+        GenericParam::Const(p) => &p.attrs,
+    }
+}
@@ -0,0 +1,47 @@
+use pub_iterator_type_attr::pub_iterator;
+
+/// An iterator over the lines of a string.
+#[pub_iterator]
+pub struct Lines<'a>(std::str::Lines<'a>);
+
+/// An iterator over the elements of a slice.
+#[pub_iterator(double_ended, exact_size, fused)]
+pub struct Iter<'a, T>(std::slice::Iter<'a, T>)
+where
+    T: 'a;
+
+/// The bytes of a string, tagged with their encoding.
+#[pub_iterator(from, debug)]
+struct Bytes<'a, E>(std::str::Bytes<'a>, std::marker::PhantomData<E>);
+enum Ascii {}
+
+/// An iterator that moves out of a vector.
+#[pub_iterator(exact_size)]
+pub(crate) type IntoIter<T> = std::vec::IntoIter<T>;
+
+#[test]
+fn basic() {
+    let lines = Lines("a\nb\nc".lines());
+    assert_eq!(vec!["a", "b", "c"], lines.collect::<Vec<_>>());
+}
+
+#[test]
+fn forwarded_traits() {
+    let mut iter = Iter([1, 2, 3].iter());
+    assert_eq!(3, iter.len());
+    assert_eq!(Some(&3), iter.next_back());
+    assert_eq!(vec![&2, &1], iter.by_ref().rev().collect::<Vec<_>>());
+    let mut fused = iter.fuse();
+    assert_eq!(None, fused.next());
+
+    let bytes: Bytes<Ascii> = "ab".bytes().into();
+    assert_eq!("Bytes { .. }", format!("{:?}", bytes));
+    assert_eq!(vec![b'a', b'b'], bytes.collect::<Vec<_>>());
+}
+
+#[test]
+fn type_alias() {
+    let iter = IntoIter(vec![1, 2].into_iter());
+    assert_eq!(2, iter.len());
+    assert_eq!(vec![1, 2], iter.collect::<Vec<_>>());
+}
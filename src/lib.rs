@@ -5,14 +5,63 @@
 // Version 2, as published by Sam Hocevar. See the COPYING file for
 // more details.
 
+//! Abstract an iterator implementation behind a struct tuple.
+//!
+//! The generated code only depends on `core`, so the crate can be
+//! used in `#![no_std]` crates. `pub_boxed_iterator_type!` also
+//! needs `alloc`, and can be disabled with the `alloc` feature.
+//!
+//! The `pub-iterator-type-attr` crate provides the same as a
+//! `#[pub_iterator]` attribute.
+//!
+//! `Resliced` provides an iterator over an owned value, as the lines
+//! of a `String`, to be hidden by the macro.
+
+#![no_std]
+
+// Paths starting with `::core` are resolved relatively to the crate
+// root in the 2015 edition, where `core` is not declared in `std`
+// crates, so the generated code uses this reexport instead.
+#[doc(hidden)]
+pub extern crate core as __core;
+
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub extern crate alloc as __alloc;
+#[cfg(feature = "stream")]
+#[doc(hidden)]
+pub extern crate futures_core as __futures_core;
+#[cfg(feature = "rayon")]
+#[doc(hidden)]
+pub extern crate rayon as __rayon;
+
+use core::fmt;
+use core::ops::{Index, RangeFrom};
+
 /// Abstract behind a tuple struct an iterator.  Usefull to privatize
 /// implementation details about the implementation of the iterator.
 /// The syntax is done to be as if you write a type definition.
 ///
-/// First you put the (non optional) doc inside `#[doc="..."]`. Then
-/// the name of your type with its generic parameter between
-/// `[]`. After `=` you put the real type that should be hidden, with
-/// an optional `where` clause.
+/// First you put the doc inside `#[doc="..."]` (optional, but
+/// mandatory for public types if you use `#![deny(missing_docs)]`),
+/// possibly followed by other attributes for the struct, as
+/// `#[derive(Clone)]`. The `#[cfg(...)]` attributes are also applied
+/// to the generated impls, and a `#[must_use]` attribute replaces the
+/// default `#[must_use = "iterators are lazy and do nothing unless
+/// consumed"]`. Then the name of your type, preceded by an optional
+/// visibility (`pub` by default, `pub(self)` for a private type),
+/// with its generic parameters between `[]`, written as between `<>`
+/// in a type definition. After `=` you put the real type that
+/// should be hidden, possibly followed by `, PhantomData<...>` for
+/// the unused generic parameters, with an optional `where` clause,
+/// and an optional block of methods to generate. The type of the
+/// items can also be given explicitly with `-> Item` before the `=`,
+/// keeping the hidden type out of the `Iterator` implementation.
+///
+/// **Beware**: unlike a Rust item, a declaration without visibility
+/// is `pub`, the type must be declared `pub(self)` to be private to
+/// its module. The generated methods stay private without visibility,
+/// as usual.
 ///
 /// # Example
 ///
@@ -36,34 +85,3757 @@
 /// }
 /// # }
 /// ```
-#[macro_export]
-macro_rules! pub_iterator_type {
-    ( #[$($attr:tt)*] $Name:ident [ $($NameParam:tt)* ] = $From:ty ) => {
-        #[$($attr)*]
-        #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
-        pub struct $Name < $($NameParam)* > ( $From );
-        impl< $($NameParam)* > Iterator for $Name < $($NameParam)* > {
-            type Item = < $From as Iterator>::Item;
-            fn next(&mut self) -> Option<Self::Item> {
-                self.0.next()
-            }
-            fn size_hint(&self) -> (usize, Option<usize>) {
-                self.0.size_hint()
-            }
-        }
-    };
-    ( #[$($attr:tt)*] $Name:ident [ $($NameParam:tt)* ] = $From:ty where $($w:tt)* ) => {
-        #[$($attr)*]
-        #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
-        pub struct $Name < $($NameParam)* > ( $From ) where $($w)* ;
-        impl< $($NameParam)* > Iterator for $Name < $($NameParam)* > where $($w)* {
-            type Item = <$From as Iterator>::Item;
-            fn next(&mut self) -> Option<Self::Item> {
-                self.0.next()
-            }
-            fn size_hint(&self) -> (usize, Option<usize>) {
-                self.0.size_hint()
-            }
-        }
-    }
+///
+/// Without any attribute:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     Undocumented[] = std::ops::Range<u32>
+/// }
+///
+/// # fn main() {
+/// assert_eq!(vec![0, 1, 2], Undocumented(0..3).collect::<Vec<_>>());
+/// # }
+/// ```
+///
+/// With lifetimes and bounds in the generic parameters:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub struct Evens<'a, T: 'a>(std::slice::Iter<'a, T>);
+/// impl<'a, T: 'a> Iterator for Evens<'a, T> {
+///     type Item = &'a T;
+///     fn next(&mut self) -> Option<&'a T> {
+///         let res = self.0.next();
+///         self.0.next();
+///         res
+///     }
+/// }
+///
+/// pub_iterator_type! {
+///     #[doc="An iterator over the lines of a string."]
+///     Lines['a] = std::str::Lines<'a>;
+///     #[doc="An iterator over clones of every other element of a slice."]
+///     EveryOther['a, T: 'a + Clone] = std::iter::Cloned<Evens<'a, T>>;
+/// }
+///
+/// # fn main() {
+/// assert_eq!(vec!["a", "b"], Lines("a\nb".lines()).collect::<Vec<_>>());
+/// let every_other = EveryOther(Evens([1, 2, 3].iter()).cloned());
+/// assert_eq!(vec![1, 3], every_other.collect::<Vec<_>>());
+/// # }
+/// ```
+///
+/// The item type of a borrowing iterator refers to its lifetime, as
+/// `&'a T` for the iterators over a slice:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="An iterator over the elements of a slice."]
+///     Iter['a, T] = std::slice::Iter<'a, T>;
+///     #[doc="An iterator over the mutable elements of a slice."]
+///     IterMut['a, T] = std::slice::IterMut<'a, T>;
+/// }
+/// pub fn first<'a, T>(iter: &mut Iter<'a, T>) -> Option<&'a T> { iter.next() }
+///
+/// # fn main() {
+/// let mut v = vec![1, 2, 3];
+/// for i in IterMut(v.iter_mut()) {
+///     *i *= 10;
+/// }
+/// let mut iter = Iter(v.iter());
+/// let first = first(&mut iter);
+/// assert_eq!(vec![&20, &30], iter.collect::<Vec<_>>());
+/// assert_eq!(Some(&10), first);
+/// # }
+/// ```
+///
+/// The bounds can contain `?Sized` and lifetimes:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="An iterator borrowing another, possibly unsized, iterator."]
+///     ByRef['a, I: ?Sized + Iterator + 'a] = &'a mut I : DoubleEndedIterator
+///     where I: DoubleEndedIterator;
+///     #[doc="Pairs of elements of two slices."]
+///     Pairs['a, 'b: 'a, T: 'b] = std::iter::Zip<std::slice::Iter<'a, T>, std::slice::Iter<'b, T>>;
+/// }
+///
+/// # fn main() {
+/// let mut numbers = 0..5;
+/// {
+///     let dynamic: &mut dyn DoubleEndedIterator<Item = u32> = &mut numbers;
+///     assert_eq!(vec![4, 3], ByRef(dynamic).rev().take(2).collect::<Vec<_>>());
+/// }
+/// assert_eq!(0..3, numbers);
+/// let (a, b) = ([1, 2], [3, 4]);
+/// let pairs = Pairs(a.iter().zip(b.iter()));
+/// assert_eq!(vec![(&1, &3), (&2, &4)], pairs.collect::<Vec<_>>());
+/// # }
+/// ```
+///
+/// As `&mut I` is an iterator if `I` is, the hidden type can borrow
+/// an iterator, consuming only the items taken through the wrapper:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="The items of an iterator, while borrowing it."]
+///     Draining['a, I] = &'a mut I where I: Iterator;
+///     #[doc="The elements of a vector, while borrowing its iterator."]
+///     Elements['a, T] = &'a mut std::vec::IntoIter<T>;
+/// }
+///
+/// # fn main() {
+/// let mut numbers = 0..10;
+/// assert_eq!(vec![0, 1, 2], Draining(&mut numbers).take(3).collect::<Vec<_>>());
+/// assert_eq!(Some(3), Draining(&mut numbers).next());
+/// assert_eq!(Some(4), numbers.next());
+///
+/// let mut iter = vec![1, 2, 3].into_iter();
+/// assert_eq!(Some(1), Elements(&mut iter).next());
+/// assert_eq!(vec![2, 3], iter.collect::<Vec<_>>());
+/// # }
+/// ```
+///
+/// The type parameters can have a default value, only used for the
+/// struct:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="Some values, `u32` by default."]
+///     Values[T = u32] = std::vec::IntoIter<T>;
+///     #[doc="Some cloned values, `u8` by default."]
+///     Cloned['a, T: 'a + Clone = u8] = std::iter::Cloned<std::slice::Iter<'a, T>>;
+/// }
+/// pub fn small() -> Values { Values(vec![1, 2].into_iter()) }
+/// pub fn big() -> Values<u64> { Values(vec![1 << 40].into_iter()) }
+/// pub fn bytes(b: &[u8]) -> Cloned { Cloned(b.iter().cloned()) }
+///
+/// # fn main() {
+/// assert_eq!(vec![1u32, 2], small().collect::<Vec<_>>());
+/// assert_eq!(vec![1u64 << 40], big().collect::<Vec<_>>());
+/// assert_eq!(vec![1u8, 2], bytes(&[1, 2]).collect::<Vec<_>>());
+/// # }
+/// ```
+///
+/// With const generic parameters:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+/// use std::convert::TryInto;
+///
+/// pub struct Chunks<'a, T: 'a, const N: usize>(&'a [T]);
+/// impl<'a, T, const N: usize> Iterator for Chunks<'a, T, N> {
+///     type Item = &'a [T; N];
+///     fn next(&mut self) -> Option<&'a [T; N]> {
+///         if self.0.len() < N {
+///             return None;
+///         }
+///         let (chunk, rest) = self.0.split_at(N);
+///         self.0 = rest;
+///         chunk.try_into().ok()
+///     }
+/// }
+///
+/// pub_iterator_type! {
+///     #[doc="An iterator over arrays of `N` elements of a slice."]
+///     ArrayChunks['a, T, const N: usize] = Chunks<'a, T, N>
+/// }
+///
+/// # fn main() {
+/// let chunks: ArrayChunks<_, 2> = ArrayChunks(Chunks(&[1, 2, 3, 4, 5]));
+/// assert_eq!(vec![&[1, 2], &[3, 4]], chunks.collect::<Vec<_>>());
+/// # }
+/// ```
+///
+/// The const parameters can also have a default value:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub struct Cells<T, const W: usize = 8>(std::vec::IntoIter<T>, usize);
+/// impl<T, const W: usize> Iterator for Cells<T, W> {
+///     type Item = ((usize, usize), T);
+///     fn next(&mut self) -> Option<Self::Item> {
+///         let i = self.1;
+///         self.1 += 1;
+///         self.0.next().map(|cell| ((i / W, i % W), cell))
+///     }
+/// }
+///
+/// pub_iterator_type! {
+///     #[doc="An iterator over the cells of a grid, 8 cells wide by default."]
+///     Grid[T, const W: usize = 8] = Cells<T, W>
+/// }
+/// pub fn grid<T, const W: usize>(cells: Vec<T>) -> Grid<T, W> {
+///     Grid(Cells(cells.into_iter(), 0))
+/// }
+///
+/// # fn main() {
+/// let default: Grid<char> = grid(vec!['a'; 9]);
+/// assert_eq!(Some(((1, 0), 'a')), default.last());
+/// let explicit: Grid<char, 3> = grid(vec!['a'; 4]);
+/// assert_eq!(Some(((1, 0), 'a')), explicit.last());
+/// # }
+/// ```
+///
+/// Mixing lifetimes and several type parameters, as for the iterators
+/// of the standard collections:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+/// use std::collections::hash_map::{self, HashMap};
+///
+/// pub_iterator_type! {
+///     #[doc="The keys of a map."]
+///     Keys['a, K, V] = hash_map::Keys<'a, K, V> : ExactSizeIterator + FusedIterator;
+///     #[doc="The values of a map."]
+///     #[derive(Clone)]
+///     Values['a, K: 'a, V: 'a] = hash_map::Values<'a, K, V> : ExactSizeIterator;
+///     #[doc="The entries of a map."]
+///     #[derive(Debug)]
+///     Iter['a, K, V] = hash_map::Iter<'a, K, V> : ExactSizeIterator;
+/// }
+///
+/// # fn main() {
+/// let map: HashMap<_, _> = vec![(1, "a"), (2, "b")].into_iter().collect();
+/// let mut keys = Keys(map.keys()).collect::<Vec<&i32>>();
+/// keys.sort();
+/// assert_eq!(vec![&1, &2], keys);
+/// let values = Values(map.values());
+/// assert_eq!(2, values.len());
+/// let mut values = values.clone().collect::<Vec<&&str>>();
+/// values.sort();
+/// assert_eq!(vec![&"a", &"b"], values);
+/// let iter = Iter(map.iter());
+/// assert_eq!(format!("Iter({:?})", map.iter()), format!("{:?}", iter));
+/// let mut entries = iter.collect::<Vec<(&i32, &&str)>>();
+/// entries.sort();
+/// assert_eq!(vec![(&1, &"a"), (&2, &"b")], entries);
+/// # }
+/// ```
+///
+/// Starting the declaration with `from_fn`, and without the hidden
+/// type, declares an iterator calling a closure on each iteration,
+/// as `std::iter::from_fn`. The type of the closure is an additional
+/// last generic parameter, and the type has a `new` constructor
+/// taking the closure:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="A counter."]
+///     from_fn Counter[] -> u32;
+///     #[doc="Clones of a value."]
+///     from_fn Clones[T: Clone] -> T;
+/// }
+/// pub fn counter() -> Counter<impl FnMut() -> Option<u32>> {
+///     let mut count = 0;
+///     Counter::new(move || {
+///         count += 1;
+///         Some(count)
+///     })
+/// }
+///
+/// # fn main() {
+/// assert_eq!(vec![1, 2, 3], counter().take(3).collect::<Vec<_>>());
+/// let mut clones = Clones::new(|| Some("a"));
+/// assert_eq!(Some("a"), clones.next());
+/// # }
+/// ```
+///
+/// The generic parameters must be used by the hidden type. If they
+/// are not, `, PhantomData<...>` after the hidden type adds a private
+/// `PhantomData` field using them, that must be given when building
+/// the value:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+/// use std::marker::PhantomData;
+///
+/// pub enum Ascii {}
+///
+/// pub_iterator_type! {
+///     #[doc="The bytes of a string, tagged with its encoding."]
+///     Bytes['a, E] = std::str::Bytes<'a>, PhantomData<E>
+/// }
+///
+/// # fn main() {
+/// let bytes: Bytes<Ascii> = Bytes("ab".bytes(), PhantomData);
+/// assert_eq!(vec![b'a', b'b'], bytes.collect::<Vec<_>>());
+/// # }
+/// ```
+///
+/// Attributes can also be given to the field of the hidden type. As
+/// for the struct, the `#[cfg(...)]` attributes are also applied to
+/// the generated impls:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="Some numbers, only iterable in debug builds."]
+///     Numbers[] = #[cfg(debug_assertions)] std::ops::Range<u32>;
+///     #[doc="Some numbers, only iterable in release builds."]
+///     Other[] = #[cfg(not(debug_assertions))] #[allow(dead_code)] std::ops::Range<u32>;
+/// }
+///
+/// # fn main() {
+/// #[cfg(debug_assertions)]
+/// assert_eq!(3, Numbers(0..3).count());
+/// #[cfg(not(debug_assertions))]
+/// assert_eq!(3, Other(0..3).count());
+/// # }
+/// ```
+///
+/// With an explicit item type:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="An iterator that moves out of a vector."]
+///     IntoIter[T] -> T = std::vec::IntoIter<T>
+/// }
+///
+/// # fn main() {
+/// let mut iter = IntoIter(vec![1, 2].into_iter());
+/// assert_eq!((2, Some(2)), iter.size_hint());
+/// assert_eq!(Some(1), iter.next());
+/// assert_eq!(Some(2), iter.next());
+/// assert_eq!(None, iter.next());
+/// # }
+/// ```
+///
+/// The explicit item type must match the one of the hidden type:
+///
+/// ```compile_fail
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="Not an iterator of `u64`."]
+///     Numbers[] -> u64 = std::ops::Range<u32>
+/// }
+/// # fn main() {}
+/// ```
+///
+/// The hidden type can also be a type parameter, only constrained on
+/// its items, the choice of the hidden type being deferred to the
+/// users of the type:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="Some items of type `T`."]
+///     Items[I, T] -> T = I where I: Iterator<Item = T>
+/// }
+/// pub fn total<I: Iterator<Item = u32>>(items: Items<I, u32>) -> u32 {
+///     items.sum()
+/// }
+///
+/// # fn main() {
+/// let from_vec: Items<std::vec::IntoIter<u32>, u32> = Items(vec![1, 2, 3].into_iter());
+/// let from_range: Items<std::ops::Range<u32>, u32> = Items(1..4);
+/// assert_eq!(6, total(from_vec));
+/// assert_eq!(6, total(from_range));
+/// # }
+/// ```
+///
+/// Unless the items are mapped by a function, given by its path
+/// after `=>`. The struct still only contains the hidden type, and
+/// the mapping is applied to each item, including with the
+/// `DoubleEndedIterator` marker. The `MinMax`, `Stream` and
+/// `ParallelIterator` markers, and the `chain`, `peekable`,
+/// `enumerate`, `take_while`, `skip_while`, `flat_map`, `cloned` and
+/// `copied` methods, can't be used with a mapping.
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="Some numbers, as `i64`."]
+///     Numbers[] -> i64 = std::ops::Range<u32> => i64::from : DoubleEndedIterator;
+///     #[doc="The parsed numbers of a string."]
+///     Parsed['a] -> u8 = std::str::Split<'a, char> => parse;
+/// }
+/// fn parse(s: &str) -> u8 { s.parse().unwrap() }
+///
+/// # fn main() {
+/// assert_eq!(vec![0i64, 1, 2], Numbers(0..3).collect::<Vec<_>>());
+/// assert_eq!(Some(2i64), Numbers(0..3).next_back());
+/// assert_eq!(Some(4i64), Numbers(0..10).nth(4));
+/// assert_eq!(6i64, Numbers(0..4).sum());
+/// assert_eq!(std::mem::size_of::<std::ops::Range<u32>>(), std::mem::size_of::<Numbers>());
+/// assert_eq!(vec![1, 20, 3], Parsed("1,20,3".split(',')).collect::<Vec<_>>());
+/// # }
+/// ```
+///
+/// The hidden type can be any type, as adapters of function pointers
+/// or trait objects:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="Some numbers, as strings."]
+///     Strings[T, U] = std::iter::Map<std::vec::IntoIter<T>, fn(T) -> U> : DoubleEndedIterator;
+///     #[doc="Some even numbers."]
+///     Evens[] = std::iter::Filter<std::ops::Range<u32>, fn(&u32) -> bool> : From;
+///     #[doc="Some boxed numbers."]
+///     Boxed['a] = Box<dyn DoubleEndedIterator<Item = (u8, Option<u8>)> + Send + 'a>
+///     : DoubleEndedIterator;
+/// }
+/// fn is_even(i: &u32) -> bool { i % 2 == 0 }
+///
+/// # fn main() {
+/// let strings = Strings(vec![1, 2].into_iter().map(|i: u32| i.to_string()));
+/// assert_eq!(vec!["2", "1"], strings.rev().collect::<Vec<_>>());
+/// let evens: Evens = (0..5).filter(is_even as fn(&u32) -> bool).into();
+/// assert_eq!(vec![0, 2, 4], evens.collect::<Vec<_>>());
+/// let boxed = Boxed(Box::new(vec![(1, None)].into_iter()));
+/// assert_eq!(vec![(1, None)], boxed.rev().collect::<Vec<_>>());
+/// # }
+/// ```
+///
+/// The `where` clause is written as in a type definition:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="Clones of the items of a reference iterator."]
+///     Cloned['a, I, T] = std::iter::Cloned<I>
+///     where I: Iterator<Item = &'a T>, T: 'a + Clone,;
+///     #[doc="A boxed iterator, possibly a trait object."]
+///     Boxed[I] = Box<I> : DoubleEndedIterator
+///     where I: ?Sized + DoubleEndedIterator;
+///     #[doc="The mapped items of an iterator."]
+///     Mapped[I, F] = std::iter::Map<I, F>
+///     where I: Iterator, I::Item: Copy, F: FnMut(I::Item) -> u8 {
+///         fn into_inner(self);
+///     }
+/// }
+///
+/// # fn main() {
+/// assert_eq!(vec![1, 2], Cloned([1, 2].iter().cloned()).collect::<Vec<_>>());
+/// let dynamic: Box<dyn DoubleEndedIterator<Item = u8>> = Box::new(0..3);
+/// assert_eq!(vec![2, 1, 0], Boxed(dynamic).rev().collect::<Vec<_>>());
+/// assert_eq!(vec![0, 2], Mapped((0..2).map(|i| i * 2)).into_inner().collect::<Vec<_>>());
+/// # }
+/// ```
+///
+/// The bounds of the generic parameters and the `where` clause can be
+/// used together, even bounding a parameter with the same trait:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="An iterator that moves out of a vector of default values."]
+///     #[derive(Clone)]
+///     Defaults[T: Clone] = std::vec::IntoIter<T> where T: Default;
+///     #[doc="An iterator that moves out of a vector of clonable values."]
+///     Clones[T: Clone + Default] = std::vec::IntoIter<T> where T: Clone, T: Default {
+///         pub fn new(iterable) -> Self;
+///     };
+/// }
+///
+/// # fn main() {
+/// let defaults = Defaults(vec![String::new(), "a".into()].into_iter());
+/// assert_eq!(vec!["", "a"], defaults.clone().collect::<Vec<_>>());
+/// assert_eq!(vec![1, 2], Clones::new(vec![1, 2]).collect::<Vec<_>>());
+/// # }
+/// ```
+///
+/// The `where` clause applies to the struct and to the impls. It can
+/// be split in sections, `where [struct]` applying to both, as the
+/// default, and `where [impl]` applying only to the impls:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="Clones of the elements of a slice."]
+///     Cloned['a, T] = std::iter::Cloned<std::slice::Iter<'a, T>>
+///     where [struct] T: 'a, where [impl] T: Clone
+/// }
+/// pub struct NotClone;
+/// pub fn no_iterator<'a>() -> Option<Cloned<'a, NotClone>> { None }
+///
+/// # fn main() {
+/// assert!(no_iterator().is_none());
+/// assert_eq!(vec![1, 2], Cloned([1, 2].iter().cloned()).collect::<Vec<_>>());
+/// # }
+/// ```
+///
+/// Bounds on the items thus go in the `where [impl]` section, the
+/// type being usable as an iterator only if they are met:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="An iterator over clonable items."]
+///     Clonable[I] = I where [impl] I: Iterator, I::Item: Clone
+/// }
+///
+/// # fn main() {
+/// let mut iter = Clonable(vec!["a", "b"].into_iter());
+/// assert_eq!(Some("a"), iter.next().clone());
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="An iterator over clonable items."]
+///     Clonable[I] = I where [impl] I: Iterator, I::Item: Clone
+/// }
+/// pub struct NotClone;
+///
+/// # fn main() {
+/// Clonable(vec![NotClone].into_iter()).next();
+/// # }
+/// ```
+///
+/// With a visibility:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// mod numbers {
+///     pub_iterator_type! {
+///         #[doc="Public numbers."]
+///         pub Public[] = std::ops::Range<u32>
+///     }
+///     pub_iterator_type! {
+///         #[doc="Numbers public in the crate."]
+///         pub(crate) Internal[] = std::ops::Range<u32>
+///     }
+///     pub_iterator_type! {
+///         #[doc="Private numbers."]
+///         pub(self) Private[] = std::ops::Range<u32>
+///     }
+///     pub fn public() -> Public { Public(0..1) }
+///     pub(crate) fn internal() -> Internal { Internal(0..2) }
+///     pub fn private_count() -> usize { Private(0..3).count() }
+/// }
+///
+/// # fn main() {
+/// assert_eq!(1, numbers::public().count());
+/// assert_eq!(2, numbers::internal().count());
+/// assert_eq!(3, numbers::private_count());
+/// # }
+/// ```
+///
+/// The declarations can be put in a private module, `mod name { ... }`,
+/// with the items of the module re-exported (with the visibility
+/// given to the module). The hidden iterator is then only accessible
+/// in this module, even in the rest of the module of the macro
+/// invocation, so the type can only be built through the declared
+/// methods (see the "Generated methods" section). The module imports
+/// the items of its parent.
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// mod numbers {
+///     pub type Inner = std::ops::Range<u32>;
+///
+///     pub_iterator_type! {
+///         pub mod sealed {
+///             #[doc="Some numbers."]
+///             Numbers[] = Inner {
+///                 pub(crate) fn new(inner) -> Self;
+///             }
+///         }
+///     }
+///     pub fn numbers() -> Numbers { Numbers::new(0..3) }
+/// }
+///
+/// # fn main() {
+/// let numbers: numbers::Numbers = numbers::numbers();
+/// assert_eq!(3, numbers.count());
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// mod numbers {
+///     pub_iterator_type! {
+///         pub mod sealed {
+///             #[doc="Some numbers."]
+///             Numbers[] = std::ops::Range<u32>
+///         }
+///     }
+///     pub fn numbers() -> Numbers { Numbers(0..3) }
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Several types can be declared at once, separated by `;`:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="Some numbers."]
+///     Numbers[] = std::ops::Range<u32>;
+///     #[doc="An iterator that moves out of a vector."]
+///     IntoIter[T] = std::vec::IntoIter<T> where T: Clone;
+///     #[doc="An iterator over the elements of a slice."]
+///     Iter['a, T] = std::slice::Iter<'a, T> : DoubleEndedIterator;
+/// }
+///
+/// # fn main() {
+/// assert_eq!(3, Numbers(0..3).count());
+/// assert_eq!(vec![1, 2], IntoIter(vec![1, 2].into_iter()).collect::<Vec<_>>());
+/// assert_eq!(Some(&2), Iter([1, 2].iter()).next_back());
+/// # }
+/// ```
+///
+/// As any iterator, the generated type, and a mutable reference to
+/// it, implement `IntoIterator` thanks to the blanket implementation
+/// of the standard library:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="Some numbers."]
+///     Numbers[] = std::ops::Range<u32>
+/// }
+/// fn sum<I: IntoIterator<Item = u32>>(iter: I) -> u32 {
+///     iter.into_iter().sum()
+/// }
+///
+/// # fn main() {
+/// let mut v = vec![];
+/// for i in Numbers(0..3) {
+///     v.push(i);
+/// }
+/// assert_eq!(vec![0, 1, 2], v);
+/// assert_eq!(6, sum(Numbers(0..4)));
+///
+/// let mut numbers = Numbers(0..10);
+/// for i in &mut numbers {
+///     if i == 4 {
+///         break;
+///     }
+/// }
+/// assert_eq!(5 + 6 + 7 + 8 + 9, sum(&mut numbers));
+/// assert_eq!(None, numbers.next());
+/// # }
+/// ```
+///
+/// # Deriving traits
+///
+/// The attributes are applied to the struct, so deriving a trait
+/// only needs the hidden type to implement it:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="An iterator over some numbers."]
+///     #[derive(Clone)]
+///     Numbers[] = std::ops::Range<u32>
+/// }
+///
+/// # fn main() {
+/// let mut iter = Numbers(0..3);
+/// let mut copy = iter.clone();
+/// assert_eq!(Some(0), iter.next());
+/// assert_eq!(Some(1), iter.next());
+/// assert_eq!(Some(0), copy.next());
+/// assert_eq!(Some(2), iter.next());
+/// assert_eq!(Some(1), copy.next());
+/// # }
+/// ```
+///
+/// `Default` can be derived if the hidden iterator implements it:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="No numbers."]
+///     #[derive(Default)]
+///     Empty[] = std::iter::Empty<i32>
+/// }
+///
+/// #[derive(Default)]
+/// pub struct Container {
+///     pub iter: Empty,
+/// }
+///
+/// # fn main() {
+/// assert_eq!(None, Empty::default().next());
+/// assert_eq!(None, Container::default().iter.next());
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="A stepped range has no default value."]
+///     #[derive(Default)]
+///     Numbers[] = std::iter::StepBy<std::ops::Range<u32>>
+/// }
+/// # fn main() {}
+/// ```
+///
+/// In the same way, the type can be `Copy` if the hidden iterator is
+/// `Copy`, which is rare in the standard library:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// #[derive(Clone, Copy)]
+/// pub struct Countdown(u32);
+/// impl Iterator for Countdown {
+///     type Item = u32;
+///     fn next(&mut self) -> Option<u32> {
+///         self.0 = self.0.checked_sub(1)?;
+///         Some(self.0)
+///     }
+/// }
+///
+/// pub_iterator_type! {
+///     #[doc="A countdown."]
+///     #[derive(Clone, Copy)]
+///     Numbers[] = Countdown
+/// }
+///
+/// # fn main() {
+/// let numbers = Numbers(Countdown(3));
+/// let copy = numbers;
+/// assert_eq!(vec![2, 1, 0], numbers.collect::<Vec<_>>());
+/// assert_eq!(vec![2, 1, 0], copy.collect::<Vec<_>>());
+/// # }
+/// ```
+///
+/// The not yet consumed iterators can also be compared, or used as
+/// keys of a map, the hidden iterator being compared or hashed:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+/// use std::collections::HashMap;
+///
+/// pub_iterator_type! {
+///     #[doc="Some numbers."]
+///     #[derive(Clone, PartialEq, Eq, Hash)]
+///     Numbers[] = std::ops::Range<u32>
+/// }
+///
+/// # fn main() {
+/// assert!(Numbers(0..3) == Numbers(0..3));
+/// assert!(Numbers(0..3) != Numbers(0..4));
+/// let mut numbers = Numbers(0..3);
+/// numbers.next();
+/// assert!(numbers == Numbers(1..3));
+///
+/// let mut sums = HashMap::new();
+/// sums.insert(Numbers(0..3), Numbers(0..3).sum::<u32>());
+/// assert_eq!(Some(&3), sums.get(&Numbers(0..3)));
+/// assert_eq!(None, sums.get(&Numbers(0..4)));
+/// # }
+/// ```
+///
+/// # Conditional compilation
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="Some numbers, from a range in debug builds."]
+///     #[cfg(debug_assertions)]
+///     #[must_use = "Numbers is lazy"]
+///     Numbers[] = std::ops::Range<u32> : DoubleEndedIterator
+/// }
+/// pub_iterator_type! {
+///     #[doc="Some numbers, from a vector in release builds."]
+///     #[cfg(not(debug_assertions))]
+///     Numbers[] = std::vec::IntoIter<u32> : DoubleEndedIterator
+/// }
+///
+/// # fn main() {
+/// #[cfg(debug_assertions)]
+/// let iter = Numbers(0..3);
+/// #[cfg(not(debug_assertions))]
+/// let iter = Numbers(vec![0, 1, 2].into_iter());
+/// assert_eq!(vec![2, 1, 0], iter.rev().collect::<Vec<_>>());
+/// # }
+/// ```
+///
+/// As the iterators of the standard library, the generated type is
+/// `#[must_use]`:
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="Some numbers."]
+///     Numbers[] = std::ops::Range<u32>
+/// }
+///
+/// # fn main() {
+/// Numbers(0..3);
+/// # }
+/// ```
+///
+/// # Generated methods
+///
+/// A block of method declarations, without their types and bodies,
+/// can end the declaration. The following methods are available:
+///
+/// - `fn into_inner(self);` returns the hidden iterator.
+/// - `fn as_inner(&self);` returns a reference to the hidden
+///   iterator.
+/// - `fn as_inner_mut(&mut self);` returns a mutable reference to the
+///   hidden iterator.
+/// - `fn peek(&mut self);` and `fn peek_mut(&mut self);` forward to
+///   the methods of the same name, if the hidden iterator is a
+///   `Peekable`.
+/// - `fn into_boxed(self);` boxes the iterator into a
+///   `Box<dyn Iterator>`. Only available with the `alloc` feature.
+/// - `fn collect_result(self);` collects the items, that must be
+///   `Result`s, into a `Result<Vec<T>, E>`, stopping at the first
+///   error. Only available with the `alloc` feature.
+/// - `fn rev(self) -> Name;` reverses the iterator, returning a new
+///   type `Name` hiding the reversed hidden iterator, with the same
+///   generic parameters and mapping, and implementing
+///   `DoubleEndedIterator`.
+/// - `fn enumerate(self) -> Name;` yields the iteration count with
+///   the items, returning a new type `Name` hiding the enumerated
+///   hidden iterator, with the same generic parameters.
+/// - `fn peekable(self) -> Name;` makes the iterator peekable,
+///   returning a new type `Name` hiding the peekable hidden iterator,
+///   with the same generic parameters, and with the `peek` and
+///   `peek_mut` methods.
+/// - `fn chain(self, other) -> Name;` chains the iterator with
+///   anything that can be converted into an iterator with the same
+///   items, returning a new type `Name` hiding the chained iterators,
+///   with the same generic parameters and the type of the other
+///   iterator.
+/// - `fn take_while(self, predicate) -> Name;` and
+///   `fn skip_while(self, predicate) -> Name;` take or skip the items
+///   while the predicate, taking a reference to the item, is true,
+///   returning a new type `Name` hiding the hidden iterator and the
+///   predicate, with the same generic parameters and the type of the
+///   predicate.
+/// - `fn flat_map(self, f) -> Name;` maps each item to something
+///   that can be converted into an iterator, and flattens the result,
+///   returning a new type `Name` hiding the flattened iterator, with
+///   the same generic parameters, the type returned by the function,
+///   and the type of the function.
+/// - `fn cloned(self) -> Name;` and `fn copied(self) -> Name;` clone
+///   or copy the items, that must be references, returning a new type
+///   `Name` hiding the cloning or copying hidden iterator, with the
+///   same generic parameters.
+/// - `fn by_ref(&mut self) -> Name;` borrows the iterator, returning
+///   a new type `Name` hiding a mutable reference to the hidden
+///   iterator, with a lifetime, the same generic parameters and the
+///   same mapping. The original iterator continues after the items
+///   taken through the borrowed one.
+/// - `fn name(inner) -> Self;`, for any `name`, builds the value from
+///   the hidden iterator.
+/// - `const fn name(inner) -> Self;`, for any `name`, is the same as
+///   `fn name(inner) -> Self;`, as a `const fn`, to build the value
+///   in a constant or a static.
+/// - `fn name(iterable) -> Self;`, for any `name`, builds the value
+///   from anything that can be converted into the hidden iterator by
+///   `IntoIterator`.
+///
+/// Each method can be preceded by a visibility, and is private by
+/// default. It can also be preceded by attributes, as `#[cfg(...)]`,
+/// `#[doc(hidden)]` or doc comments, that are put on the generated
+/// method. The `#[cfg(...)]` and `#[doc(hidden)]` attributes are also
+/// put on the generated impl, and on the generated type if any, so
+/// hiding a method as `rev` also hides the reversed type.
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="Some numbers, only built in this crate."]
+///     Numbers[] = std::ops::Range<u32> {
+///         #[doc(hidden)]
+///         pub fn new(inner) -> Self;
+///         /// Iterates from the last number.
+///         pub fn rev(self) -> RevNumbers;
+///         #[cfg(debug_assertions)]
+///         pub fn as_inner(&self);
+///     }
+/// }
+///
+/// # fn main() {
+/// let numbers = Numbers::new(0..3);
+/// #[cfg(debug_assertions)]
+/// assert_eq!(&(0..3), numbers.as_inner());
+/// assert_eq!(vec![2, 1, 0], numbers.rev().collect::<Vec<_>>());
+/// # }
+/// ```
+///
+/// `impl IntoIterator for &Self;` implements `IntoIterator` for
+/// references to the type if the hidden iterator is `Clone`, iterating
+/// from a clone of the hidden iterator. The same items can thus be
+/// iterated several times:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="Some digits."]
+///     Digits[] = std::ops::Range<u8> {
+///         impl IntoIterator for &Self;
+///     }
+/// }
+///
+/// # fn main() {
+/// let digits = Digits(0..10);
+/// let mut first = vec![];
+/// for d in &digits {
+///     first.push(d);
+/// }
+/// let second: Vec<u8> = (&digits).into_iter().collect();
+/// assert_eq!((0..10).collect::<Vec<_>>(), first);
+/// assert_eq!(first, second);
+/// assert_eq!(10, digits.count());
+/// # }
+/// ```
+///
+/// The block can also generate the iterator of a collection, given a
+/// function or a closure without captures building the hidden
+/// iterator from a reference to the collection:
+///
+/// - `impl IntoIterator for &'a Collection = f;` implements
+///   `IntoIterator` for the reference to the collection.
+/// - `fn name(&'a Collection) = f;`, for any `name`, adds the method
+///   to the collection, taking `&'a self`.
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// /// A tiny collection.
+/// pub struct Buffer<T>(Vec<T>);
+///
+/// pub_iterator_type! {
+///     #[doc="An iterator over the elements of a buffer."]
+///     Iter['a, T] = std::slice::Iter<'a, T> where T: 'a {
+///         impl IntoIterator for &'a Buffer<T> = |buffer| buffer.0.iter();
+///         pub fn iter(&'a Buffer<T>) = |buffer| buffer.0.iter();
+///     }
+/// }
+///
+/// # fn main() {
+/// let buffer = Buffer(vec![1, 2, 3]);
+/// let iter: Iter<_> = buffer.iter();
+/// assert_eq!(vec![&1, &2, &3], iter.collect::<Vec<_>>());
+/// let mut sum = 0;
+/// for i in &buffer {
+///     sum += i;
+/// }
+/// assert_eq!(6, sum);
+/// # }
+/// ```
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// mod numbers {
+///     pub_iterator_type! {
+///         #[doc="Some numbers."]
+///         Numbers[] = std::ops::Range<u32> {
+///             pub(crate) fn into_inner(self);
+///             fn as_inner_mut(&mut self);
+///         }
+///     }
+///     pub fn numbers() -> Numbers {
+///         let mut numbers = Numbers(0..10);
+///         numbers.as_inner_mut().start = 5;
+///         numbers
+///     }
+/// }
+///
+/// # fn main() {
+/// let mut iter = numbers::numbers();
+/// assert_eq!(Some(5), iter.next());
+/// assert_eq!(Some(6), iter.next());
+/// let mut inner = iter.into_inner();
+/// assert_eq!(Some(7), inner.next());
+/// assert_eq!(vec![8, 9], inner.collect::<Vec<_>>());
+/// # }
+/// ```
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// mod numbers {
+///     use std::marker::PhantomData;
+///
+///     pub_iterator_type! {
+///         #[doc="Some numbers."]
+///         Numbers[] = std::ops::Range<u32> {
+///             pub fn new(inner) -> Self;
+///         };
+///         #[doc="Some numbers, tagged with a unit."]
+///         Tagged[U] = std::ops::Range<u32>, PhantomData<U> {
+///             pub(crate) fn from_range(inner) -> Self;
+///         };
+///     }
+/// }
+///
+/// # fn main() {
+/// assert_eq!(vec![0, 1], numbers::Numbers::new(0..2).collect::<Vec<_>>());
+/// let tagged: numbers::Tagged<f32> = numbers::Tagged::from_range(0..2);
+/// assert_eq!(2, tagged.count());
+/// # }
+/// ```
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="No numbers."]
+///     Empty[] = std::iter::Empty<u32> {
+///         pub const fn new(inner) -> Self;
+///     };
+///     #[doc="Some numbers."]
+///     #[derive(Clone)]
+///     Numbers[] = std::ops::Range<u32> {
+///         pub const fn from_range(inner) -> Self;
+///     };
+/// }
+/// pub const EMPTY: Empty = Empty::new(std::iter::empty());
+/// pub static DIGITS: Numbers = Numbers::from_range(0..10);
+///
+/// # fn main() {
+/// let mut empty = EMPTY;
+/// assert_eq!(None, empty.next());
+/// assert_eq!(10, DIGITS.clone().count());
+/// # }
+/// ```
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// mod iter {
+///     pub_iterator_type! {
+///         #[doc="An iterator that moves out of a vector."]
+///         IntoIter[T] = std::vec::IntoIter<T> {
+///             pub fn new(iterable) -> Self;
+///         }
+///     }
+///     pub_iterator_type! {
+///         #[doc="Any iterator."]
+///         Iter[I: Iterator] = I where I::Item: Clone {
+///             pub fn from_iterable(iterable) -> Self;
+///         }
+///     }
+/// }
+///
+/// # fn main() {
+/// assert_eq!(vec![1, 2], iter::IntoIter::new(vec![1, 2]).collect::<Vec<_>>());
+/// let from_vec = iter::Iter::from_iterable(vec![1, 2]);
+/// let from_slice = iter::Iter::from_iterable(&[3, 4]);
+/// assert_eq!(vec![1, 2], from_vec.collect::<Vec<_>>());
+/// assert_eq!(vec![&3, &4], from_slice.collect::<Vec<_>>());
+/// # }
+/// ```
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="An iterator over the elements of a slice."]
+///     Iter['a, T] = std::slice::Iter<'a, T> : DoubleEndedIterator {
+///         pub fn rev(self) -> RevIter;
+///     }
+/// }
+///
+/// # fn main() {
+/// let v = vec![1, 2, 3];
+/// let rev: RevIter<_> = Iter(v.iter()).rev();
+/// assert_eq!(v.iter().rev().collect::<Vec<_>>(), rev.collect::<Vec<_>>());
+/// let mut rev = Iter(v.iter()).rev();
+/// assert_eq!(Some(&1), rev.next_back());
+/// assert_eq!(Some(&2), rev.rev().next());
+/// # }
+/// ```
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="An iterator over the elements of a slice."]
+///     Iter['a, T] = std::slice::Iter<'a, T> {
+///         pub fn copied(self) -> CopiedIter;
+///         pub fn cloned(self) -> ClonedIter;
+///     }
+/// }
+/// pub fn numbers(v: &[i32]) -> CopiedIter<i32> { Iter(v.iter()).copied() }
+///
+/// # fn main() {
+/// let v = vec![1, 2, 3];
+/// let copied: Vec<i32> = numbers(&v).collect();
+/// assert_eq!(v, copied);
+/// let strings = vec!["a".to_string(), "b".to_string()];
+/// let cloned: Vec<String> = Iter(strings.iter()).cloned().collect();
+/// assert_eq!(strings, cloned);
+/// # }
+/// ```
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="An iterator that moves out of a vector."]
+///     IntoIter[T] = std::vec::IntoIter<T> {
+///         pub fn chain(self, other) -> Chained;
+///     };
+///     #[doc="Some numbers."]
+///     Numbers[] = std::ops::Range<u32> {
+///         pub fn peekable(self) -> PeekableNumbers;
+///         pub fn enumerate(self) -> EnumeratedNumbers;
+///         pub fn take_while(self, predicate) -> SmallNumbers;
+///         pub fn skip_while(self, predicate) -> BigNumbers;
+///     };
+///     #[doc="Some nested vectors."]
+///     Nested[T] = std::vec::IntoIter<Vec<T>> {
+///         pub fn flat_map(self, f) -> Flattened;
+///     };
+/// }
+/// pub fn both<T>(a: Vec<T>, b: Vec<T>) -> Chained<T, IntoIter<T>> {
+///     IntoIter(a.into_iter()).chain(IntoIter(b.into_iter()))
+/// }
+/// pub fn small(limit: u32) -> SmallNumbers<impl FnMut(&u32) -> bool> {
+///     Numbers(0..10).take_while(move |&x| x < limit)
+/// }
+/// pub fn flatten<T>(v: Vec<Vec<T>>) -> Flattened<T, Vec<T>, fn(Vec<T>) -> Vec<T>> {
+///     Nested(v.into_iter()).flat_map(std::convert::identity)
+/// }
+///
+/// # fn main() {
+/// assert_eq!(vec![1, 2, 3], both(vec![1], vec![2, 3]).collect::<Vec<_>>());
+/// let chained = IntoIter(vec![1].into_iter()).chain(Some(2));
+/// assert_eq!(vec![1, 2], chained.collect::<Vec<_>>());
+///
+/// let mut numbers: PeekableNumbers = Numbers(0..3).peekable();
+/// assert_eq!(Some(&0), numbers.peek());
+/// assert_eq!(Some(0), numbers.next());
+/// if let Some(n) = numbers.peek_mut() {
+///     *n = 10;
+/// }
+/// assert_eq!(vec![10, 2], numbers.collect::<Vec<_>>());
+///
+/// let enumerated: EnumeratedNumbers = Numbers(5..8).enumerate();
+/// assert_eq!((5..8).enumerate().collect::<Vec<_>>(), enumerated.collect::<Vec<_>>());
+///
+/// let taken = Numbers(0..10).take_while(|&x| x < 5);
+/// assert_eq!(vec![0, 1, 2, 3, 4], taken.collect::<Vec<_>>());
+/// let skipped = Numbers(0..10).skip_while(|&x| x < 5);
+/// assert_eq!(vec![5, 6, 7, 8, 9], skipped.collect::<Vec<_>>());
+/// assert_eq!(3, small(3).count());
+///
+/// let nested = vec![vec![1, 2], vec![], vec![3]];
+/// let raw = nested.clone().into_iter().flat_map(|v| v).collect::<Vec<_>>();
+/// assert_eq!(raw, flatten(nested.clone()).collect::<Vec<_>>());
+/// let doubled = Nested(nested.into_iter()).flat_map(|v| v.into_iter().map(|i| i * 2));
+/// assert_eq!(vec![2, 4, 6], doubled.collect::<Vec<_>>());
+/// # }
+/// ```
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="An iterator that moves out of a vector."]
+///     IntoIter[T] = std::vec::IntoIter<T> {
+///         pub fn by_ref(&mut self) -> Borrowed;
+///     }
+/// }
+/// pub fn take_two<T>(iter: Borrowed<T>) -> Vec<T> { iter.take(2).collect() }
+///
+/// # fn main() {
+/// let mut iter = IntoIter(vec![1, 2, 3, 4].into_iter());
+/// assert_eq!(vec![1, 2], take_two(iter.by_ref()));
+/// assert_eq!(Some(3), iter.by_ref().next());
+/// assert_eq!(vec![4], iter.collect::<Vec<_>>());
+/// # }
+/// ```
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="A peekable iterator over the elements of a slice."]
+///     Iter['a, T] = std::iter::Peekable<std::slice::Iter<'a, T>> where T: 'a {
+///         pub fn peek(&mut self);
+///     };
+///     #[doc="A peekable iterator over some numbers."]
+///     Numbers[] = std::iter::Peekable<std::vec::IntoIter<u32>> {
+///         pub fn peek_mut(&mut self);
+///     };
+/// }
+///
+/// # fn main() {
+/// let v = vec![1, 2];
+/// let mut iter = Iter(v.iter().peekable());
+/// assert_eq!(Some(&&1), iter.peek());
+/// assert_eq!(Some(&&1), iter.peek());
+/// assert_eq!(Some(&1), iter.next());
+/// assert_eq!(Some(&&2), iter.peek());
+///
+/// let mut numbers = Numbers(vec![1, 2].into_iter().peekable());
+/// if let Some(n) = numbers.peek_mut() {
+///     *n = 42;
+/// }
+/// assert_eq!(vec![42, 2], numbers.collect::<Vec<_>>());
+/// # }
+/// ```
+///
+/// # Forwarding other iterator traits
+///
+/// The `Iterator` implementation forwards `next`, `size_hint`,
+/// `count`, `last`, `nth`, `fold`, `for_each`, `sum` and `product`
+/// to the hidden type, so its specialized implementations are used.
+/// `try_fold` and `try_for_each` are also forwarded with the `TryFold`
+/// marker, and `advance_by` with the `AdvanceBy` marker. Without this
+/// marker, skipping items still stays efficient through `nth`, as
+/// skipping in a range without iterating over it.
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="A lot of numbers."]
+///     Numbers[] = std::ops::Range<u64>
+/// }
+///
+/// # fn main() {
+/// assert_eq!(u64::max_value() as usize, Numbers(0..u64::max_value()).count());
+/// assert_eq!(Some(41), Numbers(0..u64::max_value()).nth(41));
+/// let mut numbers = Numbers(0..u64::max_value());
+/// assert_eq!(Some(1_000_000_000_000), numbers.nth(1_000_000_000_000));
+/// assert_eq!(Some(1_000_000_000_001), numbers.next());
+/// # }
+/// ```
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="Some numbers."]
+///     Numbers[] = std::ops::Range<i64>;
+///     #[doc="Some unordered items."]
+///     Floats[] = std::vec::IntoIter<f64>;
+/// }
+///
+/// # fn main() {
+/// assert_eq!(4950, Numbers(0..100).sum::<i64>());
+/// assert_eq!(362_880, Numbers(1..10).product::<i64>());
+/// assert_eq!(3., Floats(vec![1., 2.].into_iter()).sum::<f64>());
+/// # }
+/// ```
+///
+/// Collecting the iterator thus preallocates using the forwarded
+/// `size_hint`, and the `FromIterator` implementations based on
+/// `for_each` (as `String` from `char`s) use the forwarded `fold`.
+/// The specializations keyed on the concrete hidden type, as
+/// collecting a `std::vec::IntoIter` in place, are not available
+/// through the wrapper; `TrustedLen` can be forwarded with its marker.
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="An iterator over the elements of a slice."]
+///     Iter['a, T] = std::slice::Iter<'a, T>
+/// }
+///
+/// # fn main() {
+/// let v: Vec<u64> = (0..10_000).collect();
+/// let direct = v.iter().fold(0, |acc, &i| acc ^ (acc << 1) ^ i);
+/// let wrapped = Iter(v.iter()).fold(0, |acc, &i| acc ^ (acc << 1) ^ i);
+/// assert_eq!(direct, wrapped);
+/// # }
+/// ```
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="Some numbers."]
+///     Numbers[] = std::iter::Chain<std::ops::Range<u32>, std::ops::Range<u32>>
+/// }
+///
+/// # fn main() {
+/// let mut v = vec![];
+/// Numbers((0..2).chain(5..7)).for_each(|i| v.push(i));
+/// assert_eq!(vec![0, 1, 5, 6], v);
+///
+/// let mut numbers = Numbers((0..2).chain(5..7));
+/// let res = numbers.try_for_each(|i| if i == 5 { Err(i) } else { Ok(()) });
+/// assert_eq!(Err(5), res);
+/// assert_eq!(Some(6), numbers.next());
+/// # }
+/// ```
+///
+/// Only `Iterator` is implemented by default, as the hidden type may
+/// not implement anything else. After the hidden type, you can add
+/// `:` followed by a `+` separated list of markers to also forward
+/// these traits:
+///
+/// - `DoubleEndedIterator`: forwards `next_back`, `nth_back` and
+///   `rfold`, `try_rfold` with the `TryFold` marker, and
+///   `advance_back_by` with the `AdvanceBy` marker.
+/// - `ExactSizeIterator`: forwards `len`, and `is_empty` with the
+///   `IsEmpty` marker.
+/// - `ExactSizeHint`: as `ExactSizeIterator`, and also computes
+///   `size_hint` from `len`, checking in debug builds that the size
+///   hint of the hidden type is exact. It can't be given with
+///   `ExactSizeIterator`, that it already implements.
+/// - `UpperBound(bound)`: clamps the size hint of the hidden type to
+///   `bound`, a `usize` expression evaluated at each call to
+///   `size_hint`, when more is known about the number of items than
+///   the hidden type does. It can't be given with `ExactSizeHint`,
+///   both setting `size_hint`, nor twice.
+/// - `FusedIterator`: implements the marker trait, requiring the
+///   hidden type to be a `FusedIterator`.
+/// - `TrustedLen`: implements the marker trait, requiring the hidden
+///   type to be `TrustedLen`. Only available with the `trusted_len`
+///   feature (nightly only, the crate using the macro must then also
+///   enable `#![feature(trusted_len)]`).
+/// - `Stream`: implements `futures_core::Stream`, each call to
+///   `poll_next` being immediately ready with the next item. The
+///   hidden type doesn't need to be `Unpin`, as it is never pinned:
+///   the code around the generated type must not expose a
+///   `Pin<&mut _>` to it (e.g. with `Pin::map_unchecked_mut`). Only
+///   available with the `stream` feature.
+/// - `ParallelIterator`: implements `rayon::iter::ParallelIterator`
+///   instead of `Iterator`, forwarding `drive_unindexed` and
+///   `opt_len`. Only available with the `rayon` feature.
+/// - `MinMax`: also forwards `min` and `max` in the `Iterator`
+///   implementation, requiring the items to be `Ord`.
+/// - `TryFold`: also forwards `try_fold` and `try_for_each` in the
+///   `Iterator` implementation, and `try_rfold` in the
+///   `DoubleEndedIterator` one. Only available with the
+///   `try_trait_v2` feature (nightly only, the crate using the macro
+///   must then also enable `#![feature(try_trait_v2)]`).
+/// - `AdvanceBy`: also forwards `advance_by` in the `Iterator`
+///   implementation, and `advance_back_by` in the
+///   `DoubleEndedIterator` one. Only available with the `advance_by`
+///   feature (nightly only, the crate using the macro must then also
+///   enable `#![feature(iter_advance_by)]`).
+/// - `IsEmpty`: also forwards `is_empty` in the `ExactSizeIterator`
+///   implementation. Only available with the `exact_size_is_empty`
+///   feature (nightly only, the crate using the macro must then also
+///   enable `#![feature(exact_size_is_empty)]`).
+/// - `From`: implements `From` of the hidden type, to build the value
+///   with `.into()`.
+/// - `Send` and `Sync`: checks at compile time that the type
+///   implements these auto traits. For a generic type, the `where`
+///   clause must be enough to ensure it.
+/// - `Debug`: implements `Debug` without showing the hidden
+///   iterator, thus without requiring it to be `Debug`.
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="An iterator over the elements of a slice."]
+///     Iter['a, T] = std::slice::Iter<'a, T> : DoubleEndedIterator
+/// }
+///
+/// # fn main() {
+/// let iter = Iter([1, 2, 3].iter());
+/// assert_eq!(vec![&3, &2, &1], iter.rev().collect::<Vec<_>>());
+/// let v: Vec<u64> = (0..10_000).collect();
+/// let direct = v.iter().rfold(0, |acc, &i| acc ^ (acc << 1) ^ i);
+/// let wrapped = Iter(v.iter()).rfold(0, |acc, &i| acc ^ (acc << 1) ^ i);
+/// assert_eq!(direct, wrapped);
+///
+/// // Skipping from both ends without iterating, as the slice iterator.
+/// let v: Vec<u32> = (0..1_000_000).collect();
+/// let mut iter = Iter(v.iter());
+/// assert_eq!(Some(&10), iter.nth(10));
+/// assert_eq!(Some(&999_989), iter.nth_back(10));
+/// assert_eq!(Some(&11), iter.next());
+/// assert_eq!(Some(&999_988), iter.next_back());
+/// # }
+/// ```
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="An iterator that moves out of a vector."]
+///     IntoIter[T] = std::vec::IntoIter<T> : ExactSizeIterator
+/// }
+///
+/// # fn main() {
+/// let mut iter = IntoIter(vec![1, 2, 3].into_iter());
+/// assert_eq!(3, iter.len());
+/// iter.next();
+/// assert_eq!(2, iter.len());
+/// iter.next();
+/// iter.next();
+/// assert_eq!(0, iter.len());
+/// # }
+/// ```
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="An iterator that moves out of an array."]
+///     IntoIter[T, const N: usize] = std::array::IntoIter<T, N> : ExactSizeHint
+/// }
+///
+/// # fn main() {
+/// let mut iter = IntoIter(IntoIterator::into_iter([1, 2, 3]));
+/// assert_eq!((3, Some(3)), iter.size_hint());
+/// iter.next();
+/// assert_eq!((2, Some(2)), iter.size_hint());
+/// assert_eq!(2, iter.len());
+/// # }
+/// ```
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub const MAX_EVENS: usize = 5;
+///
+/// pub_iterator_type! {
+///     #[doc="Ten times a value."]
+///     Ten[] = std::iter::Take<std::iter::Repeat<u8>> : UpperBound(10);
+///     #[doc="The even numbers of a range."]
+///     Evens[] = std::iter::Filter<std::ops::Range<u32>, fn(&u32) -> bool>
+///     : UpperBound(MAX_EVENS);
+/// }
+/// fn is_even(i: &u32) -> bool { i % 2 == 0 }
+///
+/// # fn main() {
+/// assert_eq!((10, Some(10)), Ten(std::iter::repeat(1).take(10)).size_hint());
+/// assert_eq!((0, Some(5)), Evens((0..10).filter(is_even as fn(&u32) -> bool)).size_hint());
+/// assert_eq!((0, Some(2)), Evens((0..2).filter(is_even as fn(&u32) -> bool)).size_hint());
+/// # }
+/// ```
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub struct Unprintable;
+/// impl Iterator for Unprintable {
+///     type Item = ();
+///     fn next(&mut self) -> Option<()> { None }
+/// }
+///
+/// pub_iterator_type! {
+///     #[doc="An iterator that can be printed."]
+///     Printable[] = Unprintable : Debug
+/// }
+///
+/// # fn main() {
+/// assert_eq!("Printable { .. }", format!("{:?}", Printable(Unprintable)));
+/// # }
+/// ```
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="Some numbers."]
+///     Numbers[] = std::ops::Range<i64> : MinMax
+/// }
+///
+/// # fn main() {
+/// assert_eq!(Some(0), Numbers(0..100).min());
+/// assert_eq!(Some(99), Numbers(0..100).max());
+/// assert_eq!(None, Numbers(0..0).max());
+/// # }
+/// ```
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+/// use std::marker::PhantomData;
+///
+/// pub_iterator_type! {
+///     #[doc="An iterator that moves out of a vector."]
+///     IntoIter[T] = std::vec::IntoIter<T> : From;
+///     #[doc="The bytes of a string, tagged with their encoding."]
+///     Bytes['a, E] = std::str::Bytes<'a>, PhantomData<E> : From;
+/// }
+/// pub enum Ascii {}
+///
+/// # fn main() {
+/// let iter: IntoIter<_> = vec![1, 2].into_iter().into();
+/// assert_eq!(vec![1, 2], iter.collect::<Vec<_>>());
+/// let bytes: Bytes<Ascii> = "ab".bytes().into();
+/// assert_eq!(vec![b'a', b'b'], bytes.collect::<Vec<_>>());
+/// # }
+/// ```
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="Some numbers, that can be sent to another thread."]
+///     Numbers[] = std::ops::Range<u32> : Send + Sync;
+///     #[doc="An iterator that moves out of a vector."]
+///     IntoIter[T] = std::vec::IntoIter<T> : Send where T: Send;
+/// }
+///
+/// # fn main() {
+/// let numbers = Numbers(0..3);
+/// assert_eq!(3, std::thread::spawn(move || numbers.count()).join().unwrap());
+/// # }
+/// ```
+///
+/// The markers can be combined, each one adding its implementation,
+/// and can be followed by a `where` clause. A marker can be preceded
+/// by attributes, as `#[cfg(...)]`, that are put on its
+/// implementation, making the forward conditional. The `MinMax`,
+/// `ExactSizeHint`, `UpperBound` and `ParallelIterator` markers, that
+/// change the `Iterator` implementation itself, can't have
+/// attributes:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_iterator_type! {
+///     #[doc="An iterator over the elements of a slice."]
+///     Iter['a, T] = std::slice::Iter<'a, T>
+///         : DoubleEndedIterator + ExactSizeIterator + FusedIterator
+///         where T: 'a;
+///     #[doc="An iterator over the elements of a slice, printable in debug builds."]
+///     Elements['a, T] = std::slice::Iter<'a, T>
+///         : ExactSizeIterator + #[cfg(debug_assertions)] Debug
+///         where T: 'a;
+/// }
+///
+/// # fn main() {
+/// let v = [1, 2, 3, 4];
+/// let mut iter = Iter(v.iter());
+/// assert_eq!(4, iter.len());
+/// assert_eq!(Some(&4), iter.next_back());
+/// assert_eq!(3, iter.len());
+/// assert_eq!(vec![&3, &2, &1], iter.by_ref().rev().collect::<Vec<_>>());
+/// assert_eq!(0, iter.len());
+/// let mut fused = iter.fuse();
+/// assert_eq!(None, fused.next());
+/// assert_eq!(None, fused.next_back());
+///
+/// let elements = Elements(v.iter());
+/// assert_eq!(4, elements.len());
+/// #[cfg(debug_assertions)]
+/// assert_eq!("Elements { .. }", format!("{:?}", elements));
+/// # }
+/// ```
+///
+/// Requesting a forward that the hidden type doesn't implement is an
+/// error:
+///
+/// ```compile_fail
+/// #[macro_use] extern crate pub_iterator_type;
+/// use std::rc::Rc;
+///
+/// pub_iterator_type! {
+///     #[doc="Not `Send`."]
+///     Shared[] = std::vec::IntoIter<Rc<u32>> : Send
+/// }
+/// # fn main() {}
+/// ```
+///
+/// ```compile_fail
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub struct Flaky;
+/// impl Iterator for Flaky {
+///     type Item = ();
+///     fn next(&mut self) -> Option<()> { None }
+/// }
+///
+/// pub_iterator_type! {
+///     #[doc="Not fused."]
+///     Wrapper[] = Flaky : FusedIterator
+/// }
+/// # fn main() {}
+/// ```
+///
+/// # Hygiene
+///
+/// The generated code only uses absolute paths, so the macro can be
+/// used in modules shadowing the prelude or without it:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// mod shadowing {
+///     pub struct Option;
+///     pub struct Iterator;
+///     pub trait DoubleEndedIterator {}
+///
+///     pub_iterator_type! {
+///         #[doc="Some numbers."]
+///         Numbers[] = ::std::ops::Range<u32> : DoubleEndedIterator + Debug
+///     }
+///     pub fn numbers() -> Numbers { Numbers(0..3) }
+/// }
+///
+/// mod without_prelude {
+///     #![no_implicit_prelude]
+///
+///     pub_iterator_type! {
+///         #[doc="Some numbers."]
+///         Numbers[] = ::std::ops::Range<u32> : ExactSizeIterator + FusedIterator
+///     }
+///     pub fn numbers() -> Numbers { Numbers(0..3) }
+/// }
+///
+/// # fn main() {
+/// assert_eq!(vec![2, 1, 0], shadowing::numbers().rev().collect::<Vec<_>>());
+/// assert_eq!(3, without_prelude::numbers().len());
+/// # }
+/// ```
+///
+/// These paths are relative to this crate, through its own reexport
+/// of `core`, and thus don't depend on what `::core` or `::std` are
+/// in the crate using the macro. There is then no path to configure,
+/// even when `core` is remapped at the root of the crate:
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub mod core {
+///     pub mod iter {
+///         pub trait Iterator {}
+///     }
+///     pub mod option {
+///         pub enum Option<T> { Some(T), None }
+///     }
+/// }
+///
+/// pub_iterator_type! {
+///     #[doc="Some numbers."]
+///     Numbers[] = ::std::ops::Range<u32> : DoubleEndedIterator + ExactSizeIterator
+/// }
+///
+/// # fn main() {
+/// assert_eq!(3, Numbers(0..3).len());
+/// assert_eq!(Some(2), Numbers(0..3).next_back());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! pub_iterator_type {
+    (@item [] $From:ty) => { <$From as $crate::__core::iter::Iterator>::Item };
+    (@item [$Item:ty] $From:ty) => { $Item };
+    (@iterator [$(#[$($a:tt)*])+ ParallelIterator $($Markers:tt)*] $($state:tt)*) => {
+        $crate::__core::compile_error!(
+            "the `ParallelIterator` marker changes the `Iterator` impl, it can't have attributes"
+        );
+        $crate::pub_iterator_type! { @iterator [$($Markers)*] $($state)* }
+    };
+    (@iterator [$(#[$($a:tt)*])+ MinMax $($Markers:tt)*] $($state:tt)*) => {
+        $crate::__core::compile_error!(
+            "the `MinMax` marker changes the `Iterator` impl, it can't have attributes"
+        );
+        $crate::pub_iterator_type! { @iterator [$($Markers)*] $($state)* }
+    };
+    (@iterator [$(#[$($a:tt)*])+ ExactSizeHint $($Markers:tt)*] $($state:tt)*) => {
+        $crate::__core::compile_error!(
+            "the `ExactSizeHint` marker changes the `Iterator` impl, it can't have attributes"
+        );
+        $crate::pub_iterator_type! { @iterator [$($Markers)*] $($state)* }
+    };
+    (@iterator [$(#[$($a:tt)*])+ UpperBound ( $($bound:tt)* ) $($Markers:tt)*]
+     $($state:tt)*) => {
+        $crate::__core::compile_error!(
+            "the `UpperBound` marker changes the `Iterator` impl, it can't have attributes"
+        );
+        $crate::pub_iterator_type! { @iterator [$($Markers)*] $($state)* }
+    };
+    (@iterator [$(#[$($a:tt)*])* ParallelIterator $($Markers:tt)*]
+     $min_max:tt $size_hint:tt $ctx:tt) => {};
+    (@iterator [$(#[$($a:tt)*])* MinMax $($Markers:tt)*]
+     $min_max:tt $size_hint:tt $ctx:tt) => {
+        $crate::pub_iterator_type! { @iterator [$($Markers)*] [MinMax] $size_hint $ctx }
+    };
+    (@iterator [$(#[$($a:tt)*])* ExactSizeHint $($Markers:tt)*]
+     $min_max:tt [ExactSizeHint] $ctx:tt) => {
+        $crate::__core::compile_error!("the `ExactSizeHint` marker can only be given once");
+        $crate::pub_iterator_type! { @iterator [$($Markers)*] $min_max [ExactSizeHint] $ctx }
+    };
+    (@iterator [$(#[$($a:tt)*])* UpperBound ( $($bound:tt)* ) $($Markers:tt)*]
+     $min_max:tt [UpperBound $prev:tt] $ctx:tt) => {
+        $crate::__core::compile_error!("the `UpperBound` marker can only be given once");
+        $crate::pub_iterator_type! {
+            @iterator [$($Markers)*] $min_max [UpperBound $prev] $ctx
+        }
+    };
+    (@iterator [$(#[$($a:tt)*])* ExactSizeHint $($Markers:tt)*]
+     $min_max:tt [ $($size_hint:tt)+ ] $ctx:tt) => {
+        $crate::__core::compile_error!(
+            "the `ExactSizeHint` and `UpperBound` markers both set `size_hint`, give only one"
+        );
+        $crate::pub_iterator_type! { @iterator [$($Markers)*] $min_max [$($size_hint)+] $ctx }
+    };
+    (@iterator [$(#[$($a:tt)*])* UpperBound ( $($bound:tt)* ) $($Markers:tt)*]
+     $min_max:tt [ $($size_hint:tt)+ ] $ctx:tt) => {
+        $crate::__core::compile_error!(
+            "the `ExactSizeHint` and `UpperBound` markers both set `size_hint`, give only one"
+        );
+        $crate::pub_iterator_type! { @iterator [$($Markers)*] $min_max [$($size_hint)+] $ctx }
+    };
+    (@iterator [$(#[$($a:tt)*])* ExactSizeHint $($Markers:tt)*]
+     $min_max:tt $size_hint:tt $ctx:tt) => {
+        $crate::pub_iterator_type! { @iterator [$($Markers)*] $min_max [ExactSizeHint] $ctx }
+    };
+    (@iterator [$(#[$($a:tt)*])* UpperBound ( $bound:expr ) $($Markers:tt)*]
+     $min_max:tt $size_hint:tt $ctx:tt) => {
+        $crate::pub_iterator_type! {
+            @iterator [$($Markers)*] $min_max [UpperBound ($bound)] $ctx
+        }
+    };
+    (@iterator [$(#[$($a:tt)*])* $Marker:ident ( $($args:tt)* ) $($Markers:tt)*]
+     $min_max:tt $size_hint:tt $ctx:tt) => {
+        $crate::pub_iterator_type! { @iterator [$($Markers)*] $min_max $size_hint $ctx }
+    };
+    (@iterator [$(#[$($a:tt)*])* $Marker:ident $($Markers:tt)*]
+     $min_max:tt $size_hint:tt $ctx:tt) => {
+        $crate::pub_iterator_type! { @iterator [$($Markers)*] $min_max $size_hint $ctx }
+    };
+    (@iterator [] $min_max:tt $size_hint:tt
+     [$cfg:tt $Name:ident $ImplParam:tt $NameArg:tt [ => $map:path ] $w:tt $From:ty
+      [ $($all:tt)* ]]) => {
+        $crate::__core::compile_error!($crate::__core::concat!(
+            "a mapping needs an explicit item type, as `",
+            $crate::__core::stringify!($Name),
+            "[...] -> Item = ...`"
+        ));
+    };
+    (@iterator [] $min_max:tt [ $($size_hint:tt)* ]
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $Item:ty => $map:path ] [ $($w:tt)* ] $From:ty [ $($all:tt)* ]]) => {
+        $crate::pub_iterator_type! {
+            @assert_iterator [[$($cfg)*] $Name [$($ImplParam)*] [$($NameArg)*] [$($w)*] $From []]
+        }
+        $($cfg)*
+        impl< $($ImplParam)* > $crate::__core::iter::Iterator for $Name < $($NameArg)* >
+        where $($w)* {
+            type Item = $Item;
+            #[inline]
+            fn next(&mut self) -> $crate::__core::option::Option<Self::Item> {
+                $crate::__core::option::Option::map(
+                    $crate::__core::iter::Iterator::next(&mut self.0),
+                    $map,
+                )
+            }
+            $crate::pub_iterator_type! { @size_hint $($size_hint)* }
+            #[inline]
+            fn count(self) -> usize {
+                $crate::__core::iter::Iterator::count(self.0)
+            }
+            #[inline]
+            fn last(self) -> $crate::__core::option::Option<Self::Item> {
+                $crate::__core::option::Option::map(
+                    $crate::__core::iter::Iterator::last(self.0),
+                    $map,
+                )
+            }
+            #[inline]
+            fn nth(&mut self, n: usize) -> $crate::__core::option::Option<Self::Item> {
+                $crate::__core::option::Option::map(
+                    $crate::__core::iter::Iterator::nth(&mut self.0, n),
+                    $map,
+                )
+            }
+            #[inline]
+            fn fold<__B, __F>(self, init: __B, mut f: __F) -> __B
+            where __F: $crate::__core::ops::FnMut(__B, Self::Item) -> __B {
+                $crate::__core::iter::Iterator::fold(self.0, init, move |acc, item| {
+                    f(acc, $map(item))
+                })
+            }
+            $crate::pub_iterator_type! { @forward Iterator [$map] [$($all)*] }
+        }
+    };
+    (@iterator [] [ $($min_max:tt)* ] [ $($size_hint:tt)* ]
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($Item:ty)? ] [ $($w:tt)* ] $From:ty [ $($all:tt)* ]]) => {
+        $crate::pub_iterator_type! {
+            @assert_iterator [[$($cfg)*] $Name [$($ImplParam)*] [$($NameArg)*] [$($w)*] $From []]
+        }
+        $($cfg)*
+        impl< $($ImplParam)* > $crate::__core::iter::Iterator for $Name < $($NameArg)* >
+        where $From: $crate::__core::iter::Iterator $(<Item = $Item>)?, $($w)* {
+            type Item = $crate::pub_iterator_type!(@item [$($Item)?] $From);
+            #[inline]
+            fn next(&mut self) -> $crate::__core::option::Option<Self::Item> {
+                $crate::__core::iter::Iterator::next(&mut self.0)
+            }
+            $crate::pub_iterator_type! { @size_hint $($size_hint)* }
+            #[inline]
+            fn count(self) -> usize {
+                $crate::__core::iter::Iterator::count(self.0)
+            }
+            #[inline]
+            fn last(self) -> $crate::__core::option::Option<Self::Item> {
+                $crate::__core::iter::Iterator::last(self.0)
+            }
+            #[inline]
+            fn nth(&mut self, n: usize) -> $crate::__core::option::Option<Self::Item> {
+                $crate::__core::iter::Iterator::nth(&mut self.0, n)
+            }
+            #[inline]
+            fn fold<__B, __F>(self, init: __B, f: __F) -> __B
+            where __F: $crate::__core::ops::FnMut(__B, Self::Item) -> __B {
+                $crate::__core::iter::Iterator::fold(self.0, init, f)
+            }
+            #[inline]
+            fn for_each<__F>(self, f: __F) where __F: $crate::__core::ops::FnMut(Self::Item) {
+                $crate::__core::iter::Iterator::for_each(self.0, f)
+            }
+            #[inline]
+            fn sum<__S>(self) -> __S where __S: $crate::__core::iter::Sum<Self::Item> {
+                $crate::__core::iter::Iterator::sum(self.0)
+            }
+            #[inline]
+            fn product<__P>(self) -> __P
+            where __P: $crate::__core::iter::Product<Self::Item> {
+                $crate::__core::iter::Iterator::product(self.0)
+            }
+            $crate::pub_iterator_type! { @min_max $($min_max)* }
+            $crate::pub_iterator_type! { @forward Iterator [] [$($all)*] }
+        }
+    };
+    // Checked apart from the `Iterator` implementation, for the error
+    // to point to the hidden type when it is not an iterator.
+    (@assert_iterator
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($phantom:tt)* ]]) => {
+        $($cfg)*
+        #[doc(hidden)]
+        const _: () = {
+            fn assert<__T: $crate::__core::iter::Iterator + ?$crate::__core::marker::Sized>() {}
+            #[allow(dead_code)]
+            fn check< $($ImplParam)* >(_: $Name < $($NameArg)* >) where $($w)* {
+                assert::<$From>()
+            }
+        };
+    };
+    (@size_hint) => {
+        #[inline]
+        fn size_hint(&self) -> (usize, $crate::__core::option::Option<usize>) {
+            $crate::__core::iter::Iterator::size_hint(&self.0)
+        }
+    };
+    (@size_hint ExactSizeHint) => {
+        #[inline]
+        fn size_hint(&self) -> (usize, $crate::__core::option::Option<usize>) {
+            let len = $crate::__core::iter::ExactSizeIterator::len(&self.0);
+            $crate::__core::debug_assert_eq!(
+                (len, $crate::__core::option::Option::Some(len)),
+                $crate::__core::iter::Iterator::size_hint(&self.0),
+                "the size hint of the hidden iterator is not exact"
+            );
+            (len, $crate::__core::option::Option::Some(len))
+        }
+    };
+    (@size_hint UpperBound ($bound:expr)) => {
+        #[inline]
+        fn size_hint(&self) -> (usize, $crate::__core::option::Option<usize>) {
+            let bound: usize = $bound;
+            let (lower, upper) = $crate::__core::iter::Iterator::size_hint(&self.0);
+            let upper = match upper {
+                $crate::__core::option::Option::Some(upper) if upper < bound => upper,
+                _ => bound,
+            };
+            ($crate::__core::cmp::min(lower, bound), $crate::__core::option::Option::Some(upper))
+        }
+    };
+    (@min_max) => {};
+    (@min_max MinMax) => {
+        #[inline]
+        fn min(self) -> $crate::__core::option::Option<Self::Item>
+        where Self::Item: $crate::__core::cmp::Ord {
+            $crate::__core::iter::Iterator::min(self.0)
+        }
+        #[inline]
+        fn max(self) -> $crate::__core::option::Option<Self::Item>
+        where Self::Item: $crate::__core::cmp::Ord {
+            $crate::__core::iter::Iterator::max(self.0)
+        }
+    };
+    // The methods of a trait forwarded only with a marker, as
+    // `TryFold`, looked for in all the markers of the declaration.
+    (@forward $Trait:ident $map:tt []) => {};
+    (@forward $Trait:ident $map:tt
+     [$(#[$($a:tt)*])* $Marker:ident ( $($args:tt)* ) $($Markers:tt)*]) => {
+        $crate::pub_iterator_type! { @forward $Trait $map [$($Markers)*] }
+    };
+    (@forward $Trait:ident $map:tt [$(#[$($a:tt)*])* $Marker:ident $($Markers:tt)*]) => {
+        $crate::pub_iterator_type! { @forward_method $Trait $Marker $map [$(#[$($a)*])*] }
+        $crate::pub_iterator_type! { @forward $Trait $map [$($Markers)*] }
+    };
+    (@forward_method Iterator TryFold [] [ $($attr:tt)* ]) => {
+        $crate::__pub_iterator_type_if_try_trait_v2! {
+            $($attr)*
+            #[inline]
+            fn try_fold<__B, __F, __R>(&mut self, init: __B, f: __F) -> __R
+            where
+                __F: $crate::__core::ops::FnMut(__B, Self::Item) -> __R,
+                __R: $crate::__core::ops::Try<Output = __B>,
+            {
+                $crate::__core::iter::Iterator::try_fold(&mut self.0, init, f)
+            }
+            $($attr)*
+            #[inline]
+            fn try_for_each<__F, __R>(&mut self, f: __F) -> __R
+            where
+                __F: $crate::__core::ops::FnMut(Self::Item) -> __R,
+                __R: $crate::__core::ops::Try<Output = ()>,
+            {
+                $crate::__core::iter::Iterator::try_for_each(&mut self.0, f)
+            }
+        }
+    };
+    (@forward_method Iterator TryFold [$map:path] [ $($attr:tt)* ]) => {
+        $crate::__pub_iterator_type_if_try_trait_v2! {
+            $($attr)*
+            #[inline]
+            fn try_fold<__B, __F, __R>(&mut self, init: __B, mut f: __F) -> __R
+            where
+                __F: $crate::__core::ops::FnMut(__B, Self::Item) -> __R,
+                __R: $crate::__core::ops::Try<Output = __B>,
+            {
+                $crate::__core::iter::Iterator::try_fold(&mut self.0, init, move |acc, item| {
+                    f(acc, $map(item))
+                })
+            }
+            $($attr)*
+            #[inline]
+            fn try_for_each<__F, __R>(&mut self, mut f: __F) -> __R
+            where
+                __F: $crate::__core::ops::FnMut(Self::Item) -> __R,
+                __R: $crate::__core::ops::Try<Output = ()>,
+            {
+                $crate::__core::iter::Iterator::try_for_each(&mut self.0, move |item| f($map(item)))
+            }
+        }
+    };
+    (@forward_method DoubleEndedIterator TryFold [] [ $($attr:tt)* ]) => {
+        $crate::__pub_iterator_type_if_try_trait_v2! {
+            $($attr)*
+            #[inline]
+            fn try_rfold<__B, __F, __R>(&mut self, init: __B, f: __F) -> __R
+            where
+                __F: $crate::__core::ops::FnMut(__B, Self::Item) -> __R,
+                __R: $crate::__core::ops::Try<Output = __B>,
+            {
+                $crate::__core::iter::DoubleEndedIterator::try_rfold(&mut self.0, init, f)
+            }
+        }
+    };
+    (@forward_method DoubleEndedIterator TryFold [$map:path] [ $($attr:tt)* ]) => {
+        $crate::__pub_iterator_type_if_try_trait_v2! {
+            $($attr)*
+            #[inline]
+            fn try_rfold<__B, __F, __R>(&mut self, init: __B, mut f: __F) -> __R
+            where
+                __F: $crate::__core::ops::FnMut(__B, Self::Item) -> __R,
+                __R: $crate::__core::ops::Try<Output = __B>,
+            {
+                $crate::__core::iter::DoubleEndedIterator::try_rfold(
+                    &mut self.0,
+                    init,
+                    move |acc, item| f(acc, $map(item)),
+                )
+            }
+        }
+    };
+    (@forward_method Iterator AdvanceBy $map:tt [ $($attr:tt)* ]) => {
+        $crate::__pub_iterator_type_if_advance_by! {
+            $($attr)*
+            #[inline]
+            fn advance_by(
+                &mut self,
+                n: usize,
+            ) -> $crate::__core::result::Result<(), $crate::__core::num::NonZeroUsize> {
+                $crate::__core::iter::Iterator::advance_by(&mut self.0, n)
+            }
+        }
+    };
+    (@forward_method DoubleEndedIterator AdvanceBy $map:tt [ $($attr:tt)* ]) => {
+        $crate::__pub_iterator_type_if_advance_by! {
+            $($attr)*
+            #[inline]
+            fn advance_back_by(
+                &mut self,
+                n: usize,
+            ) -> $crate::__core::result::Result<(), $crate::__core::num::NonZeroUsize> {
+                $crate::__core::iter::DoubleEndedIterator::advance_back_by(&mut self.0, n)
+            }
+        }
+    };
+    (@forward_method ExactSizeIterator IsEmpty $map:tt [ $($attr:tt)* ]) => {
+        $crate::__pub_iterator_type_if_exact_size_is_empty! {
+            $($attr)*
+            #[inline]
+            fn is_empty(&self) -> bool {
+                $crate::__core::iter::ExactSizeIterator::is_empty(&self.0)
+            }
+        }
+    };
+    (@forward_method $Trait:ident $Marker:ident $map:tt $attrs:tt) => {};
+    (@markers [] $map:tt $all:tt $ctx:tt) => {};
+    (@markers [$(#[$($a:tt)*])* UpperBound ( $($bound:tt)* ) $($Markers:tt)*] $map:tt
+     $all:tt $ctx:tt) => {
+        $crate::pub_iterator_type! { @markers [$($Markers)*] $map $all $ctx }
+    };
+    (@markers [$(#[$($a:tt)*])* $Marker:ident ( $($args:tt)* ) $($Markers:tt)*] $map:tt
+     $all:tt $ctx:tt) => {
+        $crate::__core::compile_error!($crate::__core::concat!(
+            "unknown marker `",
+            $crate::__core::stringify!($Marker($($args)*)),
+            "`"
+        ));
+        $crate::pub_iterator_type! { @markers [$($Markers)*] $map $all $ctx }
+    };
+    (@markers [$(#[$($a:tt)*])* $Marker:ident $($Markers:tt)*] []
+     $all:tt [[ $($cfg:tt)* ] $($ctx:tt)*]) => {
+        $crate::pub_iterator_type! { @marker $Marker $all [[$($cfg)* $(#[$($a)*])*] $($ctx)*] }
+        $crate::pub_iterator_type! { @markers [$($Markers)*] [] $all [[$($cfg)*] $($ctx)*] }
+    };
+    (@markers [$(#[$($a:tt)*])* $Marker:ident $($Markers:tt)*] $map:tt
+     $all:tt [[ $($cfg:tt)* ] $($ctx:tt)*]) => {
+        $crate::pub_iterator_type! {
+            @mapped_marker $Marker $map $all [[$($cfg)* $(#[$($a)*])*] $($ctx)*]
+        }
+        $crate::pub_iterator_type! { @markers [$($Markers)*] $map $all [[$($cfg)*] $($ctx)*] }
+    };
+    (@mapped_marker DoubleEndedIterator [$map:path] $all:tt
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($phantom:tt)* ]]) => {
+        $($cfg)*
+        impl< $($ImplParam)* > $crate::__core::iter::DoubleEndedIterator
+        for $Name < $($NameArg)* > where $($w)* {
+            #[inline]
+            fn next_back(&mut self) -> $crate::__core::option::Option<Self::Item> {
+                $crate::__core::option::Option::map(
+                    $crate::__core::iter::DoubleEndedIterator::next_back(&mut self.0),
+                    $map,
+                )
+            }
+            #[inline]
+            fn nth_back(&mut self, n: usize) -> $crate::__core::option::Option<Self::Item> {
+                $crate::__core::option::Option::map(
+                    $crate::__core::iter::DoubleEndedIterator::nth_back(&mut self.0, n),
+                    $map,
+                )
+            }
+            #[inline]
+            fn rfold<__B, __F>(self, init: __B, mut f: __F) -> __B
+            where __F: $crate::__core::ops::FnMut(__B, Self::Item) -> __B {
+                $crate::__core::iter::DoubleEndedIterator::rfold(self.0, init, move |acc, item| {
+                    f(acc, $map(item))
+                })
+            }
+            $crate::pub_iterator_type! { @forward DoubleEndedIterator [$map] $all }
+        }
+    };
+    (@mapped_marker MinMax $map:tt $all:tt $ctx:tt) => {
+        $crate::__core::compile_error!("the `MinMax` marker can't be used with a mapping");
+    };
+    (@mapped_marker Stream $map:tt $all:tt $ctx:tt) => {
+        $crate::__core::compile_error!("the `Stream` marker can't be used with a mapping");
+    };
+    (@mapped_marker ParallelIterator $map:tt $all:tt $ctx:tt) => {
+        $crate::__core::compile_error!(
+            "the `ParallelIterator` marker can't be used with a mapping"
+        );
+    };
+    (@mapped_marker $Marker:ident $map:tt $all:tt $ctx:tt) => {
+        $crate::pub_iterator_type! { @marker $Marker $all $ctx }
+    };
+    (@marker DoubleEndedIterator $all:tt
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($phantom:tt)* ]]) => {
+        $($cfg)*
+        impl< $($ImplParam)* > $crate::__core::iter::DoubleEndedIterator
+        for $Name < $($NameArg)* > where $($w)* {
+            #[inline]
+            fn next_back(&mut self) -> $crate::__core::option::Option<Self::Item> {
+                $crate::__core::iter::DoubleEndedIterator::next_back(&mut self.0)
+            }
+            #[inline]
+            fn nth_back(&mut self, n: usize) -> $crate::__core::option::Option<Self::Item> {
+                $crate::__core::iter::DoubleEndedIterator::nth_back(&mut self.0, n)
+            }
+            #[inline]
+            fn rfold<__B, __F>(self, init: __B, f: __F) -> __B
+            where __F: $crate::__core::ops::FnMut(__B, Self::Item) -> __B {
+                $crate::__core::iter::DoubleEndedIterator::rfold(self.0, init, f)
+            }
+            $crate::pub_iterator_type! { @forward DoubleEndedIterator [] $all }
+        }
+    };
+    (@marker ExactSizeIterator $all:tt
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($phantom:tt)* ]]) => {
+        $($cfg)*
+        impl< $($ImplParam)* > $crate::__core::iter::ExactSizeIterator
+        for $Name < $($NameArg)* > where $($w)* {
+            #[inline]
+            fn len(&self) -> usize {
+                $crate::__core::iter::ExactSizeIterator::len(&self.0)
+            }
+            $crate::pub_iterator_type! { @forward ExactSizeIterator [] $all }
+        }
+    };
+    (@marker FusedIterator $all:tt
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($phantom:tt)* ]]) => {
+        $($cfg)*
+        impl< $($ImplParam)* > $crate::__core::iter::FusedIterator for $Name < $($NameArg)* >
+        where $From: $crate::__core::iter::FusedIterator, $($w)* {}
+    };
+    (@marker TrustedLen $all:tt $ctx:tt) => {
+        $crate::__pub_iterator_type_trusted_len! { $ctx }
+    };
+    (@marker Stream $all:tt $ctx:tt) => {
+        $crate::__pub_iterator_type_stream! { $ctx }
+    };
+    (@marker ParallelIterator $all:tt $ctx:tt) => {
+        $crate::__pub_iterator_type_rayon! { $ctx }
+    };
+    (@marker MinMax $all:tt $ctx:tt) => {};
+    (@marker TryFold $all:tt $ctx:tt) => {
+        $crate::__pub_iterator_type_if_try_trait_v2! { @marker }
+    };
+    (@marker AdvanceBy $all:tt $ctx:tt) => {
+        $crate::__pub_iterator_type_if_advance_by! { @marker }
+    };
+    (@marker IsEmpty $all:tt $ctx:tt) => {
+        $crate::__pub_iterator_type_if_exact_size_is_empty! { @marker }
+    };
+    (@marker ExactSizeHint $all:tt $ctx:tt) => {
+        $crate::pub_iterator_type! { @exact_size_hint $all $all $ctx }
+    };
+    // `ExactSizeHint` implements `ExactSizeIterator`, looked for in
+    // all the markers to reject it instead of implementing it twice.
+    (@exact_size_hint [] $all:tt $ctx:tt) => {
+        $crate::pub_iterator_type! { @marker ExactSizeIterator $all $ctx }
+    };
+    (@exact_size_hint [$(#[$($a:tt)*])* ExactSizeIterator $($Markers:tt)*] $all:tt $ctx:tt) => {
+        $crate::__core::compile_error!(
+            "the `ExactSizeHint` marker already implements `ExactSizeIterator`"
+        );
+    };
+    (@exact_size_hint [$(#[$($a:tt)*])* $Marker:ident ( $($args:tt)* ) $($Markers:tt)*]
+     $all:tt $ctx:tt) => {
+        $crate::pub_iterator_type! { @exact_size_hint [$($Markers)*] $all $ctx }
+    };
+    (@exact_size_hint [$(#[$($a:tt)*])* $Marker:ident $($Markers:tt)*] $all:tt $ctx:tt) => {
+        $crate::pub_iterator_type! { @exact_size_hint [$($Markers)*] $all $ctx }
+    };
+    (@marker From $all:tt
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ] [ $($w:tt)* ] $From:ty
+      [ $($Phantom:ty)? ]]) => {
+        $($cfg)*
+        impl< $($ImplParam)* > $crate::__core::convert::From<$From> for $Name < $($NameArg)* >
+        where $($w)* {
+            #[inline]
+            fn from(inner: $From) -> Self {
+                $Name(inner $(, $crate::__core::marker::PhantomData::<$Phantom>)?)
+            }
+        }
+    };
+    (@marker Send $all:tt $ctx:tt) => {
+        $crate::pub_iterator_type! { @assert Send $ctx }
+    };
+    (@marker Sync $all:tt $ctx:tt) => {
+        $crate::pub_iterator_type! { @assert Sync $ctx }
+    };
+    (@marker Debug $all:tt
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($phantom:tt)* ]]) => {
+        $($cfg)*
+        impl< $($ImplParam)* > $crate::__core::fmt::Debug for $Name < $($NameArg)* >
+        where $($w)* {
+            fn fmt(&self, f: &mut $crate::__core::fmt::Formatter) -> $crate::__core::fmt::Result {
+                f.debug_struct($crate::__core::stringify!($Name)).finish_non_exhaustive()
+            }
+        }
+    };
+    (@marker $Marker:ident $all:tt $ctx:tt) => {
+        $crate::__core::compile_error!($crate::__core::concat!(
+            "unknown marker `", $crate::__core::stringify!($Marker), "`"
+        ));
+    };
+    (@assert $Trait:ident
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($phantom:tt)* ]]) => {
+        $($cfg)*
+        #[doc(hidden)]
+        const _: () = {
+            fn assert<__T: $crate::__core::marker::$Trait>() {}
+            #[allow(dead_code)]
+            fn check< $($ImplParam)* >() where $($w)* {
+                assert::<$Name < $($NameArg)* >>()
+            }
+        };
+    };
+    (@helpers $mapping:tt $ctx:tt) => {};
+    (@helpers $mapping:tt [[ $($cfg:tt)* ] $($ctx:tt)*] $(#[$($a:tt)*])*
+     impl IntoIterator for &Self ; $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @into_iterator_ref [[$($cfg)* $(#[$($a)*])*] $($ctx)*] }
+        $crate::pub_iterator_type! { @helpers $mapping [[$($cfg)*] $($ctx)*] $($rest)* }
+    };
+    (@helpers $mapping:tt [[ $($cfg:tt)* ] $($ctx:tt)*] $(#[$($a:tt)*])*
+     impl IntoIterator for $Coll:ty = $f:expr ; $($rest:tt)*) => {
+        $crate::pub_iterator_type! {
+            @into_iterator $Coll [$f] [[$($cfg)* $(#[$($a)*])*] $($ctx)*]
+        }
+        $crate::pub_iterator_type! { @helpers $mapping [[$($cfg)*] $($ctx)*] $($rest)* }
+    };
+    (@helpers $mapping:tt [[ $($cfg:tt)* ] $($ctx:tt)*] $(#[$($a:tt)*])*
+     $v:vis fn $method:ident (& $lt:lifetime $Coll:ty) = $f:expr ; $($rest:tt)*) => {
+        $crate::pub_iterator_type! {
+            @method_attrs [] [] $(#[$($a)*])*
+            (@iter_method $method) [$v] ($lt $Coll [$f]) [[$($cfg)*] $($ctx)*]
+        }
+        $crate::pub_iterator_type! { @helpers $mapping [[$($cfg)*] $($ctx)*] $($rest)* }
+    };
+    (@helpers $mapping:tt [[ $($cfg:tt)* ] $($ctx:tt)*] $(#[$($a:tt)*])*
+     $v:vis const fn $helper:ident $args:tt $(-> $Ret:ident)? ; $($rest:tt)*) => {
+        $crate::pub_iterator_type! {
+            @method_attrs [] [] $(#[$($a)*])*
+            (@helper const $helper $args [$(-> $Ret)*]) [$v] () [[$($cfg)*] $($ctx)*]
+        }
+        $crate::pub_iterator_type! { @helpers $mapping [[$($cfg)*] $($ctx)*] $($rest)* }
+    };
+    (@helpers $mapping:tt [[ $($cfg:tt)* ] $($ctx:tt)*] $(#[$($a:tt)*])*
+     $v:vis fn $helper:ident $args:tt $(-> $Ret:ident)? ; $($rest:tt)*) => {
+        $crate::pub_iterator_type! {
+            @method_attrs [] [] $(#[$($a)*])*
+            (@mapped_helper $helper $args [$(-> $Ret)*]) [$v] ($mapping) [[$($cfg)*] $($ctx)*]
+        }
+        $crate::pub_iterator_type! { @helpers $mapping [[$($cfg)*] $($ctx)*] $($rest)* }
+    };
+    // The attributes of a method are put on the method, the
+    // `#[cfg(...)]` and `#[doc(hidden)]` ones being also put on its
+    // impl and generated type.
+    (@method_attrs [ $($cfg:tt)* ] [ $($attr:tt)* ] #[cfg $($c:tt)*] $($rest:tt)*) => {
+        $crate::pub_iterator_type! {
+            @method_attrs [$($cfg)* #[cfg $($c)*]] [$($attr)* #[cfg $($c)*]] $($rest)*
+        }
+    };
+    (@method_attrs [ $($cfg:tt)* ] [ $($attr:tt)* ] #[doc(hidden)] $($rest:tt)*) => {
+        $crate::pub_iterator_type! {
+            @method_attrs [$($cfg)* #[doc(hidden)]] [$($attr)* #[doc(hidden)]] $($rest)*
+        }
+    };
+    (@method_attrs [ $($cfg:tt)* ] [ $($attr:tt)* ] #[$($a:tt)*] $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @method_attrs [$($cfg)*] [$($attr)* #[$($a)*]] $($rest)* }
+    };
+    (@method_attrs [ $($cfg:tt)* ] [ $($attr:tt)* ] ( $($call:tt)* ) [ $($v:tt)* ]
+     ( $($args:tt)* ) [[ $($ctx_cfg:tt)* ] $($ctx:tt)*]) => {
+        $crate::pub_iterator_type! {
+            $($call)* [$($attr)* $($v)*] $($args)* [[$($ctx_cfg)* $($cfg)*] $($ctx)*]
+        }
+    };
+    (@mapped_helper rev (self) [-> $Rev:ident] $v:tt [$Item:ty => $map:path] $ctx:tt) => {
+        $crate::pub_iterator_type! { @helper rev (self) [-> $Rev, $Item => $map] $v $ctx }
+    };
+    (@mapped_helper by_ref (&mut self) [-> $Borrowed:ident] $v:tt [$Item:ty => $map:path]
+     $ctx:tt) => {
+        $crate::pub_iterator_type! {
+            @helper by_ref (&mut self) [-> $Borrowed, $Item => $map] $v $ctx
+        }
+    };
+    (@mapped_helper chain $args:tt $ret:tt $v:tt [$Item:ty => $map:path] $ctx:tt) => {
+        $crate::__core::compile_error!("the `chain` method can't be used with a mapping");
+    };
+    (@mapped_helper peekable $args:tt $ret:tt $v:tt [$Item:ty => $map:path] $ctx:tt) => {
+        $crate::__core::compile_error!("the `peekable` method can't be used with a mapping");
+    };
+    (@mapped_helper enumerate $args:tt $ret:tt $v:tt [$Item:ty => $map:path] $ctx:tt) => {
+        $crate::__core::compile_error!("the `enumerate` method can't be used with a mapping");
+    };
+    (@mapped_helper take_while $args:tt $ret:tt $v:tt [$Item:ty => $map:path] $ctx:tt) => {
+        $crate::__core::compile_error!("the `take_while` method can't be used with a mapping");
+    };
+    (@mapped_helper skip_while $args:tt $ret:tt $v:tt [$Item:ty => $map:path] $ctx:tt) => {
+        $crate::__core::compile_error!("the `skip_while` method can't be used with a mapping");
+    };
+    (@mapped_helper flat_map $args:tt $ret:tt $v:tt [$Item:ty => $map:path] $ctx:tt) => {
+        $crate::__core::compile_error!("the `flat_map` method can't be used with a mapping");
+    };
+    (@mapped_helper cloned $args:tt $ret:tt $v:tt [$Item:ty => $map:path] $ctx:tt) => {
+        $crate::__core::compile_error!("the `cloned` method can't be used with a mapping");
+    };
+    (@mapped_helper copied $args:tt $ret:tt $v:tt [$Item:ty => $map:path] $ctx:tt) => {
+        $crate::__core::compile_error!("the `copied` method can't be used with a mapping");
+    };
+    (@mapped_helper $helper:ident $args:tt $ret:tt $v:tt $mapping:tt $ctx:tt) => {
+        $crate::pub_iterator_type! { @helper $helper $args $ret $v $ctx }
+    };
+    (@into_iterator $Coll:ty [$f:expr]
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($Phantom:ty)? ]]) => {
+        $($cfg)*
+        impl< $($ImplParam)* > $crate::__core::iter::IntoIterator for $Coll where $($w)* {
+            type Item = <Self::IntoIter as $crate::__core::iter::Iterator>::Item;
+            type IntoIter = $Name < $($NameArg)* >;
+            #[inline]
+            fn into_iter(self) -> Self::IntoIter {
+                let f: fn(Self) -> $From = $f;
+                $Name(f(self) $(, $crate::__core::marker::PhantomData::<$Phantom>)?)
+            }
+        }
+    };
+    (@into_iterator_ref
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($Phantom:ty)? ]]) => {
+        $($cfg)*
+        impl<'__a, $($ImplParam)* > $crate::__core::iter::IntoIterator for &'__a $Name < $($NameArg)* >
+        where $($w)* $From: $crate::__core::clone::Clone {
+            type Item = <$Name < $($NameArg)* > as $crate::__core::iter::Iterator>::Item;
+            type IntoIter = $Name < $($NameArg)* >;
+            #[inline]
+            fn into_iter(self) -> Self::IntoIter {
+                $Name(
+                    $crate::__core::clone::Clone::clone(&self.0)
+                    $(, $crate::__core::marker::PhantomData::<$Phantom>)?
+                )
+            }
+        }
+    };
+    (@iter_method $method:ident [$(#[$($fa:tt)*])* $v:vis] $lt:lifetime $Coll:ty [$f:expr]
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($Phantom:ty)? ]]) => {
+        $($cfg)*
+        impl< $($ImplParam)* > $Coll where $($w)* {
+            #[doc = $crate::__core::concat!(
+                "Returns an iterator of type `", $crate::__core::stringify!($Name), "`."
+            )]
+            #[inline]
+            $(#[$($fa)*])*
+            $v fn $method(& $lt self) -> $Name < $($NameArg)* > {
+                let f: fn(& $lt Self) -> $From = $f;
+                $Name(f(self) $(, $crate::__core::marker::PhantomData::<$Phantom>)?)
+            }
+        }
+    };
+    (@helper into_inner (self) [] [$(#[$($fa:tt)*])* $v:vis]
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($phantom:tt)* ]]) => {
+        $($cfg)*
+        impl< $($ImplParam)* > $Name < $($NameArg)* > where $($w)* {
+            /// Returns the hidden iterator.
+            #[inline]
+            $(#[$($fa)*])*
+            $v fn into_inner(self) -> $From {
+                self.0
+            }
+        }
+    };
+    (@helper as_inner (&self) [] [$(#[$($fa:tt)*])* $v:vis]
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($phantom:tt)* ]]) => {
+        $($cfg)*
+        impl< $($ImplParam)* > $Name < $($NameArg)* > where $($w)* {
+            /// Returns a reference to the hidden iterator.
+            #[inline]
+            $(#[$($fa)*])*
+            $v fn as_inner(&self) -> &$From {
+                &self.0
+            }
+        }
+    };
+    (@helper as_inner_mut (&mut self) [] [$(#[$($fa:tt)*])* $v:vis]
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($phantom:tt)* ]]) => {
+        $($cfg)*
+        impl< $($ImplParam)* > $Name < $($NameArg)* > where $($w)* {
+            /// Returns a mutable reference to the hidden iterator.
+            #[inline]
+            $(#[$($fa)*])*
+            $v fn as_inner_mut(&mut self) -> &mut $From {
+                &mut self.0
+            }
+        }
+    };
+    (@helper peek (&mut self) [] [$(#[$($fa:tt)*])* $v:vis]
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($phantom:tt)* ]]) => {
+        $($cfg)*
+        impl< $($ImplParam)* > $Name < $($NameArg)* > where $($w)* {
+            /// Returns a reference to the next item without advancing
+            /// the iterator.
+            #[inline]
+            $(#[$($fa)*])*
+            $v fn peek(
+                &mut self,
+            ) -> $crate::__core::option::Option<&<Self as $crate::__core::iter::Iterator>::Item> {
+                $crate::__core::iter::Peekable::peek(&mut self.0)
+            }
+        }
+    };
+    (@helper peek_mut (&mut self) [] [$(#[$($fa:tt)*])* $v:vis]
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($phantom:tt)* ]]) => {
+        $($cfg)*
+        impl< $($ImplParam)* > $Name < $($NameArg)* > where $($w)* {
+            /// Returns a mutable reference to the next item without
+            /// advancing the iterator.
+            #[inline]
+            $(#[$($fa)*])*
+            $v fn peek_mut(
+                &mut self,
+            ) -> $crate::__core::option::Option<
+                &mut <Self as $crate::__core::iter::Iterator>::Item
+            > {
+                $crate::__core::iter::Peekable::peek_mut(&mut self.0)
+            }
+        }
+    };
+    (@helper into_boxed (self) [] $v:tt $ctx:tt) => {
+        $crate::__pub_iterator_type_into_boxed! { $v $ctx }
+    };
+    (@helper collect_result (self) [] $v:tt $ctx:tt) => {
+        $crate::__pub_iterator_type_collect_result! { $v $ctx }
+    };
+    (@helper __new_boxed ($Item:ty, $lt:lifetime) [] [$(#[$($fa:tt)*])* $v:vis]
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($phantom:tt)* ]]) => {
+        $($cfg)*
+        impl< $($ImplParam)* > $Name < $($NameArg)* > where $($w)* {
+            /// Boxes an iterator to hide it.
+            #[inline]
+            $(#[$($fa)*])*
+            $v fn new<__I>(iter: __I) -> Self
+            where __I: $crate::__core::iter::Iterator<Item = $Item> + $lt {
+                $Name($crate::__alloc::boxed::Box::new(iter))
+            }
+        }
+    };
+    (@helper rev (self) [-> $Rev:ident $(, $Item:ty => $map:path)?] [$(#[$($fa:tt)*])* $v:vis]
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($Phantom:ty)? ]]) => {
+        $crate::pub_iterator_type! {
+            @decl [
+                #[doc = $crate::__core::concat!(
+                    "A reversed `", $crate::__core::stringify!($Name), "`."
+                )]
+                #[must_use = "iterators are lazy and do nothing unless consumed"]
+                $($cfg)*
+            ] [$($cfg)*] [$v]
+            $Rev[$($ImplParam)*] $(-> $Item)?
+            = $crate::__core::iter::Rev<$From> $(, PhantomData<$Phantom>)? $(=> $map)?
+            : DoubleEndedIterator
+            where $($w)* where [impl] $From: $crate::__core::iter::DoubleEndedIterator
+        }
+        $($cfg)*
+        impl< $($ImplParam)* > $Name < $($NameArg)* > where $($w)* {
+            /// Reverses the direction of the iterator.
+            #[inline]
+            $(#[$($fa)*])*
+            $v fn rev(self) -> $Rev < $($NameArg)* >
+            where $From: $crate::__core::iter::DoubleEndedIterator {
+                $Rev(
+                    $crate::__core::iter::Iterator::rev(self.0)
+                    $(, $crate::__core::marker::PhantomData::<$Phantom>)?
+                )
+            }
+        }
+    };
+    (@helper enumerate (self) [-> $Enumerated:ident] [$(#[$($fa:tt)*])* $v:vis]
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($Phantom:ty)? ]]) => {
+        $crate::pub_iterator_type! {
+            @decl [
+                #[doc = $crate::__core::concat!(
+                    "An enumerated `", $crate::__core::stringify!($Name), "`."
+                )]
+                #[must_use = "iterators are lazy and do nothing unless consumed"]
+                $($cfg)*
+            ] [$($cfg)*] [$v]
+            $Enumerated[$($ImplParam)*]
+            = $crate::__core::iter::Enumerate<$From> $(, PhantomData<$Phantom>)?
+            where $($w)*
+        }
+        $($cfg)*
+        impl< $($ImplParam)* > $Name < $($NameArg)* > where $($w)* {
+            /// Yields the current iteration count with the items.
+            #[inline]
+            $(#[$($fa)*])*
+            $v fn enumerate(self) -> $Enumerated < $($NameArg)* > {
+                $Enumerated(
+                    $crate::__core::iter::Iterator::enumerate(self.0)
+                    $(, $crate::__core::marker::PhantomData::<$Phantom>)?
+                )
+            }
+        }
+    };
+    (@helper peekable (self) [-> $Peekable:ident] [$(#[$($fa:tt)*])* $v:vis]
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($Phantom:ty)? ]]) => {
+        $crate::pub_iterator_type! {
+            @decl [
+                #[doc = $crate::__core::concat!(
+                    "A peekable `", $crate::__core::stringify!($Name), "`."
+                )]
+                #[must_use = "iterators are lazy and do nothing unless consumed"]
+                $($cfg)*
+            ] [$($cfg)*] [$v]
+            $Peekable[$($ImplParam)*]
+            = $crate::__core::iter::Peekable<$From> $(, PhantomData<$Phantom>)?
+            where $($w)* $From: $crate::__core::iter::Iterator {
+                $v fn peek(&mut self);
+                $v fn peek_mut(&mut self);
+            }
+        }
+        $($cfg)*
+        impl< $($ImplParam)* > $Name < $($NameArg)* > where $($w)* {
+            /// Makes the iterator peekable.
+            #[inline]
+            $(#[$($fa)*])*
+            $v fn peekable(self) -> $Peekable < $($NameArg)* > {
+                $Peekable(
+                    $crate::__core::iter::Iterator::peekable(self.0)
+                    $(, $crate::__core::marker::PhantomData::<$Phantom>)?
+                )
+            }
+        }
+    };
+    (@helper chain (self, other) [-> $Chained:ident] [$(#[$($fa:tt)*])* $v:vis]
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($Phantom:ty)? ]]) => {
+        $crate::pub_iterator_type! {
+            @decl [
+                #[doc = $crate::__core::concat!(
+                    "A `", $crate::__core::stringify!($Name), "` chained with another iterator."
+                )]
+                #[must_use = "iterators are lazy and do nothing unless consumed"]
+                $($cfg)*
+            ] [$($cfg)*] [$v]
+            $Chained[$($ImplParam)* __I]
+            = $crate::__core::iter::Chain<$From, __I> $(, PhantomData<$Phantom>)?
+            where $($w)*
+            where [impl] __I: $crate::__core::iter::Iterator<
+                Item = <$From as $crate::__core::iter::Iterator>::Item
+            >
+        }
+        $($cfg)*
+        impl< $($ImplParam)* > $Name < $($NameArg)* > where $($w)* {
+            /// Chains the iterator with another one, iterating over
+            /// the items of the other one after its own items.
+            #[inline]
+            $(#[$($fa)*])*
+            $v fn chain<__C>(
+                self,
+                other: __C,
+            ) -> $Chained < $($NameArg)* <__C as $crate::__core::iter::IntoIterator>::IntoIter >
+            where
+                __C: $crate::__core::iter::IntoIterator<
+                    Item = <$From as $crate::__core::iter::Iterator>::Item
+                >,
+            {
+                $Chained(
+                    $crate::__core::iter::Iterator::chain(self.0, other)
+                    $(, $crate::__core::marker::PhantomData::<$Phantom>)?
+                )
+            }
+        }
+    };
+    (@helper take_while (self, predicate) $ret:tt $v:tt $ctx:tt) => {
+        $crate::pub_iterator_type! {
+            @helper_while take_while TakeWhile
+            "` taking its items while a predicate is true."
+            "Takes the items while the predicate is true."
+            $ret $v $ctx
+        }
+    };
+    (@helper skip_while (self, predicate) $ret:tt $v:tt $ctx:tt) => {
+        $crate::pub_iterator_type! {
+            @helper_while skip_while SkipWhile
+            "` skipping its items while a predicate is true."
+            "Skips the items while the predicate is true."
+            $ret $v $ctx
+        }
+    };
+    (@helper_while $method:ident $Adapter:ident $type_doc:literal $method_doc:literal
+     [-> $While:ident] [$(#[$($fa:tt)*])* $v:vis]
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($Phantom:ty)? ]]) => {
+        $crate::pub_iterator_type! {
+            @decl [
+                #[doc = $crate::__core::concat!(
+                    "A `", $crate::__core::stringify!($Name), $type_doc
+                )]
+                #[must_use = "iterators are lazy and do nothing unless consumed"]
+                $($cfg)*
+            ] [$($cfg)*] [$v]
+            $While[$($ImplParam)* __W]
+            = $crate::__core::iter::$Adapter<$From, __W> $(, PhantomData<$Phantom>)?
+            where $($w)*
+            where [impl]
+            $crate::__core::iter::$Adapter<$From, __W>: $crate::__core::iter::Iterator
+        }
+        $($cfg)*
+        impl< $($ImplParam)* > $Name < $($NameArg)* > where $($w)* {
+            #[doc = $method_doc]
+            #[inline]
+            $(#[$($fa)*])*
+            $v fn $method<__P>(self, predicate: __P) -> $While < $($NameArg)* __P >
+            where
+                __P: $crate::__core::ops::FnMut(
+                    &<$From as $crate::__core::iter::Iterator>::Item
+                ) -> bool,
+            {
+                $While(
+                    $crate::__core::iter::Iterator::$method(self.0, predicate)
+                    $(, $crate::__core::marker::PhantomData::<$Phantom>)?
+                )
+            }
+        }
+    };
+    (@helper flat_map (self, f) [-> $FlatMapped:ident] [$(#[$($fa:tt)*])* $v:vis]
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($Phantom:ty)? ]]) => {
+        $crate::pub_iterator_type! {
+            @decl [
+                #[doc = $crate::__core::concat!(
+                    "A `", $crate::__core::stringify!($Name), "` flattening its mapped items."
+                )]
+                #[must_use = "iterators are lazy and do nothing unless consumed"]
+                $($cfg)*
+            ] [$($cfg)*] [$v]
+            $FlatMapped[$($ImplParam)* __U, __M]
+            = $crate::__core::iter::FlatMap<$From, __U, __M> $(, PhantomData<$Phantom>)?
+            where $($w)* __U: $crate::__core::iter::IntoIterator
+            where [impl]
+            $crate::__core::iter::FlatMap<$From, __U, __M>: $crate::__core::iter::Iterator
+        }
+        $($cfg)*
+        impl< $($ImplParam)* > $Name < $($NameArg)* > where $($w)* {
+            /// Maps each item to an iterable, and flattens the result.
+            #[inline]
+            $(#[$($fa)*])*
+            $v fn flat_map<__U, __M>(self, f: __M) -> $FlatMapped < $($NameArg)* __U, __M >
+            where
+                __U: $crate::__core::iter::IntoIterator,
+                __M: $crate::__core::ops::FnMut(
+                    <$From as $crate::__core::iter::Iterator>::Item
+                ) -> __U,
+            {
+                $FlatMapped(
+                    $crate::__core::iter::Iterator::flat_map(self.0, f)
+                    $(, $crate::__core::marker::PhantomData::<$Phantom>)?
+                )
+            }
+        }
+    };
+    (@helper cloned (self) $ret:tt $v:tt $ctx:tt) => {
+        $crate::pub_iterator_type! {
+            @helper_owned cloned Cloned [$crate::__core::clone::Clone]
+            "` cloning its items."
+            "Clones the referenced items."
+            $ret $v $ctx
+        }
+    };
+    (@helper copied (self) $ret:tt $v:tt $ctx:tt) => {
+        $crate::pub_iterator_type! {
+            @helper_owned copied Copied [$crate::__core::marker::Copy]
+            "` copying its items."
+            "Copies the referenced items."
+            $ret $v $ctx
+        }
+    };
+    (@helper_owned $method:ident $Adapter:ident [$Trait:path]
+     $type_doc:literal $method_doc:literal
+     [-> $Owned:ident] [$(#[$($fa:tt)*])* $v:vis]
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($Phantom:ty)? ]]) => {
+        $crate::pub_iterator_type! {
+            @decl [
+                #[doc = $crate::__core::concat!(
+                    "A `", $crate::__core::stringify!($Name), $type_doc
+                )]
+                #[must_use = "iterators are lazy and do nothing unless consumed"]
+                $($cfg)*
+            ] [$($cfg)*] [$v]
+            $Owned[$($ImplParam)*]
+            = $crate::__core::iter::$Adapter<$From> $(, PhantomData<$Phantom>)?
+            where $($w)*
+            where [impl] $crate::__core::iter::$Adapter<$From>: $crate::__core::iter::Iterator
+        }
+        $($cfg)*
+        impl< $($ImplParam)* > $Name < $($NameArg)* > where $($w)* {
+            #[doc = $method_doc]
+            #[inline]
+            $(#[$($fa)*])*
+            $v fn $method<'__a, __T>(self) -> $Owned < $($NameArg)* >
+            where
+                $From: $crate::__core::iter::Iterator<Item = &'__a __T>,
+                __T: $Trait + '__a,
+            {
+                $Owned(
+                    $crate::__core::iter::Iterator::$method(self.0)
+                    $(, $crate::__core::marker::PhantomData::<$Phantom>)?
+                )
+            }
+        }
+    };
+    (@helper by_ref (&mut self) [-> $Borrowed:ident $(, $Item:ty => $map:path)?]
+     [$(#[$($fa:tt)*])* $v:vis]
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($Phantom:ty)? ]]) => {
+        $crate::pub_iterator_type! {
+            @decl [
+                #[doc = $crate::__core::concat!(
+                    "A borrowed `", $crate::__core::stringify!($Name), "`."
+                )]
+                #[must_use = "iterators are lazy and do nothing unless consumed"]
+                $($cfg)*
+            ] [$($cfg)*] [$v]
+            $Borrowed['__a, $($ImplParam)*] $(-> $Item)?
+            = &'__a mut $From $(, PhantomData<$Phantom>)? $(=> $map)?
+            where $($w)*
+        }
+        $($cfg)*
+        impl< $($ImplParam)* > $Name < $($NameArg)* > where $($w)* {
+            /// Borrows the iterator, the items taken from the borrowed
+            /// iterator being consumed from this one.
+            #[inline]
+            $(#[$($fa)*])*
+            $v fn by_ref<'__a>(&'__a mut self) -> $Borrowed < '__a, $($NameArg)* > {
+                $Borrowed(&mut self.0 $(, $crate::__core::marker::PhantomData::<$Phantom>)?)
+            }
+        }
+    };
+    (@helper __new_from_fn (closure) [] [$(#[$($fa:tt)*])* $v:vis]
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $Phantom:ty ]]) => {
+        $($cfg)*
+        impl< $($ImplParam)* > $Name < $($NameArg)* > where $($w)* {
+            /// Builds the iterator from a closure, called on each
+            /// iteration.
+            #[inline]
+            $(#[$($fa)*])*
+            $v fn new(closure: __FromFn) -> Self {
+                $Name(
+                    $crate::__core::iter::from_fn(closure),
+                    $crate::__core::marker::PhantomData::<$Phantom>,
+                )
+            }
+        }
+    };
+    (@helper $f:ident (inner) [-> Self] [$(#[$($fa:tt)*])* $v:vis]
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($Phantom:ty)? ]]) => {
+        $($cfg)*
+        impl< $($ImplParam)* > $Name < $($NameArg)* > where $($w)* {
+            /// Builds the iterator from the hidden iterator.
+            #[inline]
+            $(#[$($fa)*])*
+            $v fn $f(inner: $From) -> Self {
+                $Name(inner $(, $crate::__core::marker::PhantomData::<$Phantom>)?)
+            }
+        }
+    };
+    (@helper const $f:ident (inner) [-> Self] [$(#[$($fa:tt)*])* $v:vis]
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($Phantom:ty)? ]]) => {
+        $($cfg)*
+        impl< $($ImplParam)* > $Name < $($NameArg)* > where $($w)* {
+            /// Builds the iterator from the hidden iterator, in a
+            /// `const` context if needed.
+            #[inline]
+            $(#[$($fa)*])*
+            $v const fn $f(inner: $From) -> Self {
+                $Name(inner $(, $crate::__core::marker::PhantomData::<$Phantom>)?)
+            }
+        }
+    };
+    (@helper const $helper:ident $args:tt [ $($ret:tt)* ] $v:tt $ctx:tt) => {
+        $crate::__core::compile_error!($crate::__core::concat!(
+            "unknown const method `const fn ",
+            $crate::__core::stringify!($helper),
+            $crate::__core::stringify!($args),
+            $(" ", $crate::__core::stringify!($ret),)*
+            "`, only `const fn name(inner) -> Self;` can be const"
+        ));
+    };
+    (@helper $f:ident (iterable) [-> Self] [$(#[$($fa:tt)*])* $v:vis]
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($Phantom:ty)? ]]) => {
+        $($cfg)*
+        impl< $($ImplParam)* > $Name < $($NameArg)* > where $($w)* {
+            /// Builds the iterator from something that can be
+            /// converted into the hidden iterator.
+            #[inline]
+            $(#[$($fa)*])*
+            $v fn $f<__C>(iterable: __C) -> Self
+            where __C: $crate::__core::iter::IntoIterator<IntoIter = $From> {
+                $Name(
+                    $crate::__core::iter::IntoIterator::into_iter(iterable)
+                    $(, $crate::__core::marker::PhantomData::<$Phantom>)?
+                )
+            }
+        }
+    };
+    (@helper $helper:ident $args:tt [ $($ret:tt)* ] $v:tt $ctx:tt) => {
+        $crate::__core::compile_error!($crate::__core::concat!(
+            "unknown method `fn ",
+            $crate::__core::stringify!($helper),
+            $crate::__core::stringify!($args),
+            $(" ", $crate::__core::stringify!($ret),)*
+            "`"
+        ));
+    };
+    (@attrs [ $($attr:tt)* ] [ $($cfg:tt)* ] [ $($must_use:tt)* ]
+     #[cfg $($c:tt)*] $($rest:tt)*) => {
+        $crate::pub_iterator_type! {
+            @attrs [$($attr)* #[cfg $($c)*]] [$($cfg)* #[cfg $($c)*]] [$($must_use)*]
+            $($rest)*
+        }
+    };
+    (@attrs [ $($attr:tt)* ] [ $($cfg:tt)* ] [ $($must_use:tt)* ]
+     #[must_use $($m:tt)*] $($rest:tt)*) => {
+        $crate::pub_iterator_type! {
+            @attrs [$($attr)* #[must_use $($m)*]] [$($cfg)*] [must_use]
+            $($rest)*
+        }
+    };
+    (@attrs [ $($attr:tt)* ] [ $($cfg:tt)* ] [ $($must_use:tt)* ]
+     #[$($a:tt)*] $($rest:tt)*) => {
+        $crate::pub_iterator_type! {
+            @attrs [$($attr)* #[$($a)*]] [$($cfg)*] [$($must_use)*]
+            $($rest)*
+        }
+    };
+    (@attrs [ $($attr:tt)* ] [ $($cfg:tt)* ] [] $($rest:tt)*) => {
+        $crate::pub_iterator_type! {
+            @vis [
+                $($attr)*
+                #[must_use = "iterators are lazy and do nothing unless consumed"]
+            ] [$($cfg)*]
+            $($rest)*
+        }
+    };
+    (@attrs [ $($attr:tt)* ] [ $($cfg:tt)* ] [must_use] $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @vis [$($attr)*] [$($cfg)*] $($rest)* }
+    };
+    (@vis [ $($attr:tt)* ] [ $($cfg:tt)* ] pub ( $($v:tt)* ) $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @decl [$($attr)*] [$($cfg)*] [pub($($v)*)] $($rest)* }
+    };
+    (@vis [ $($attr:tt)* ] [ $($cfg:tt)* ] pub $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @decl [$($attr)*] [$($cfg)*] [pub] $($rest)* }
+    };
+    (@vis [ $($attr:tt)* ] [ $($cfg:tt)* ] $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @decl [$($attr)*] [$($cfg)*] [pub] $($rest)* }
+    };
+    (@decl $attr:tt $cfg:tt $vis:tt
+     from_fn $Name:ident [] -> $Item:ty $(; $($rest:tt)*)?) => {
+        $crate::pub_iterator_type! {
+            @from_fn $attr $cfg $vis $Name [__FromFn] $Item $(; $($rest)*)*
+        }
+    };
+    (@decl $attr:tt $cfg:tt $vis:tt
+     from_fn $Name:ident [ $($NameParam:tt)* ] -> $Item:ty $(; $($rest:tt)*)?) => {
+        $crate::pub_iterator_type! {
+            @from_fn_params [] [$($NameParam)*] [$attr $cfg $vis $Name] [$Item $(; $($rest)*)*]
+        }
+    };
+    // Adds the closure parameter after the given ones, with a comma only
+    // when there is not already a trailing one.
+    (@from_fn_params [ $($param:tt)* ] [ , ] [ $($before:tt)* ] [ $($after:tt)* ]) => {
+        $crate::pub_iterator_type! { @from_fn $($before)* [$($param)*, __FromFn] $($after)* }
+    };
+    (@from_fn_params [ $($param:tt)* ] [ $last:tt ] [ $($before:tt)* ] [ $($after:tt)* ]) => {
+        $crate::pub_iterator_type! { @from_fn $($before)* [$($param)* $last, __FromFn] $($after)* }
+    };
+    (@from_fn_params [ $($param:tt)* ] [ $t:tt $($rest:tt)+ ] $before:tt $after:tt) => {
+        $crate::pub_iterator_type! { @from_fn_params [$($param)* $t] [$($rest)+] $before $after }
+    };
+    (@from_fn $attr:tt $cfg:tt [ $($vis:tt)* ] $Name:ident $NameParam:tt $Item:ty
+     $(; $($rest:tt)*)?) => {
+        $crate::pub_iterator_type! {
+            @decl $attr $cfg [$($vis)*]
+            $Name $NameParam -> $Item
+            = $crate::__core::iter::FromFn<__FromFn>, PhantomData<fn() -> $Item>
+            where [impl]
+            __FromFn: $crate::__core::ops::FnMut() -> $crate::__core::option::Option<$Item>
+            {
+                $($vis)* fn __new_from_fn(closure);
+            }
+            $(; $($rest)*)*
+        }
+    };
+    (@decl [ $($attr:tt)* ] [ $($cfg:tt)* ] [ $($vis:tt)* ]
+     $Name:ident [ $($NameParam:tt)* ] $(-> $Item:ty)? = $($rest:tt)*) => {
+        $crate::pub_iterator_type! {
+            @generics [[[$($attr)*] [$($cfg)*] [$($vis)*] $Name] [[$($Item)*]] [$($rest)*] [] [] []]
+            $($NameParam)*
+        }
+    };
+    (@generics [[ $($before:tt)* ] [ $($after:tt)* ] [ $($rest:tt)* ]
+                [ $($Param:tt)* ] [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]]) => {
+        $crate::pub_iterator_type! {
+            @field [$($before)* [$($Param)*] [$($ImplParam)*] [$($NameArg)*] $($after)*] [] []
+            $($rest)*
+        }
+    };
+    (@generics [$before:tt $after:tt $rest:tt
+                [ $($Param:tt)* ] [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]]
+     $lt:lifetime $(: $bound:lifetime $(+ $bounds:lifetime)*)? $(, $($params:tt)*)?) => {
+        $crate::pub_iterator_type! {
+            @generics [$before $after $rest
+                       [$($Param)* $lt $(: $bound $(+ $bounds)*)*,]
+                       [$($ImplParam)* $lt $(: $bound $(+ $bounds)*)*,]
+                       [$($NameArg)* $lt,]]
+            $($($params)*)*
+        }
+    };
+    (@generics [$before:tt $after:tt $rest:tt
+                [ $($Param:tt)* ] [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]]
+     const $N:ident : $Ty:ty $(= $Default:tt)? $(, $($params:tt)*)?) => {
+        $crate::pub_iterator_type! {
+            @generics [$before $after $rest
+                       [$($Param)* const $N: $Ty $(= $Default)?,]
+                       [$($ImplParam)* const $N: $Ty,]
+                       [$($NameArg)* $N,]]
+            $($($params)*)*
+        }
+    };
+    (@generics $state:tt $T:ident : $($params:tt)*) => {
+        $crate::pub_iterator_type! { @bounds $state $T [] [] $($params)* }
+    };
+    (@generics [$before:tt $after:tt $rest:tt
+                [ $($Param:tt)* ] [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]]
+     $T:ident = $Default:ty $(, $($params:tt)*)?) => {
+        $crate::pub_iterator_type! {
+            @generics [$before $after $rest
+                       [$($Param)* $T = $Default,] [$($ImplParam)* $T,] [$($NameArg)* $T,]]
+            $($($params)*)*
+        }
+    };
+    (@generics [$before:tt $after:tt $rest:tt
+                [ $($Param:tt)* ] [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]]
+     $T:ident $(, $($params:tt)*)?) => {
+        $crate::pub_iterator_type! {
+            @generics [$before $after $rest
+                       [$($Param)* $T,] [$($ImplParam)* $T,] [$($NameArg)* $T,]]
+            $($($params)*)*
+        }
+    };
+    (@bounds [$before:tt $after:tt $rest:tt
+              [ $($Param:tt)* ] [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]]
+     $T:ident [ $($bound:tt)* ] [] $(, $($params:tt)*)?) => {
+        $crate::pub_iterator_type! {
+            @generics [$before $after $rest
+                       [$($Param)* $T: $($bound)*,]
+                       [$($ImplParam)* $T: $($bound)*,]
+                       [$($NameArg)* $T,]]
+            $($($params)*)*
+        }
+    };
+    (@bounds [$before:tt $after:tt $rest:tt
+              [ $($Param:tt)* ] [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]]
+     $T:ident [ $($bound:tt)* ] [] = $Default:ty $(, $($params:tt)*)?) => {
+        $crate::pub_iterator_type! {
+            @generics [$before $after $rest
+                       [$($Param)* $T: $($bound)* = $Default,]
+                       [$($ImplParam)* $T: $($bound)*,]
+                       [$($NameArg)* $T,]]
+            $($($params)*)*
+        }
+    };
+    (@bounds $state:tt $T:ident [ $($bound:tt)* ] [ $($depth:tt)* ] < $($params:tt)*) => {
+        $crate::pub_iterator_type! { @bounds $state $T [$($bound)* <] [< $($depth)*] $($params)* }
+    };
+    (@bounds $state:tt $T:ident [ $($bound:tt)* ] [ < $($depth:tt)* ] > $($params:tt)*) => {
+        $crate::pub_iterator_type! { @bounds $state $T [$($bound)* >] [$($depth)*] $($params)* }
+    };
+    (@bounds $state:tt $T:ident [ $($bound:tt)* ] [ < < $($depth:tt)* ] >> $($params:tt)*) => {
+        $crate::pub_iterator_type! { @bounds $state $T [$($bound)* >>] [$($depth)*] $($params)* }
+    };
+    (@bounds $state:tt $T:ident [ $($bound:tt)* ] [ $($depth:tt)* ] $t:tt $($params:tt)*) => {
+        $crate::pub_iterator_type! { @bounds $state $T [$($bound)* $t] [$($depth)*] $($params)* }
+    };
+    (@field [ $($decl:tt)* ] [ $($attr:tt)* ] [ $($cfg:tt)* ] #[cfg $($c:tt)*] $($rest:tt)*) => {
+        $crate::pub_iterator_type! {
+            @field [$($decl)*] [$($attr)* #[cfg $($c)*]] [$($cfg)* #[cfg $($c)*]] $($rest)*
+        }
+    };
+    (@field [ $($decl:tt)* ] [ $($attr:tt)* ] [ $($cfg:tt)* ] #[$($a:tt)*] $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @field [$($decl)*] [$($attr)* #[$($a)*]] [$($cfg)*] $($rest)* }
+    };
+    (@field [ $($decl:tt)* ] [ $($attr:tt)* ] [ $($cfg:tt)* ] $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @from [$($decl)* [$($attr)*] [$($cfg)*]] $($rest)* }
+    };
+    (@from [ $($decl:tt)* ] $From:ty $(, PhantomData<$Phantom:ty>)? $(=> $map:path)?
+     $(: $(#[$($ma:tt)*])* $Marker:ident $(($($marg:tt)*))?
+         $(+ $(#[$($mas:tt)*])* $Markers:ident $(($($margs:tt)*))?)*)?
+     where $($rest:tt)*) => {
+        $crate::pub_iterator_type! {
+            @where [
+                $($decl)* $From [$($Phantom)?] [$($map)?]
+                [$(
+                    $(#[$($ma)*])* $Marker $(($($marg)*))*
+                    $($(#[$($mas)*])* $Markers $(($($margs)*))*)*
+                )*]
+            ] [] [] [struct] []
+            $($rest)*
+        }
+    };
+    (@from [ $($decl:tt)* ] $From:ty $(, PhantomData<$Phantom:ty>)? $(=> $map:path)?
+     $(: $(#[$($ma:tt)*])* $Marker:ident $(($($marg:tt)*))?
+         $(+ $(#[$($mas:tt)*])* $Markers:ident $(($($margs:tt)*))?)*)?
+     { $($helpers:tt)* } $($rest:tt)*) => {
+        $crate::pub_iterator_type! {
+            @where [
+                $($decl)* $From [$($Phantom)?] [$($map)?]
+                [$(
+                    $(#[$($ma)*])* $Marker $(($($marg)*))*
+                    $($(#[$($mas)*])* $Markers $(($($margs)*))*)*
+                )*]
+            ] [] [] [struct] []
+            { $($helpers)* } $($rest)*
+        }
+    };
+    (@from [ $($decl:tt)* ] $From:ty $(, PhantomData<$Phantom:ty>)? $(=> $map:path)?
+     $(: $(#[$($ma:tt)*])* $Marker:ident $(($($marg:tt)*))?
+         $(+ $(#[$($mas:tt)*])* $Markers:ident $(($($margs:tt)*))?)*)?
+     ; $($rest:tt)*) => {
+        $crate::pub_iterator_type! {
+            @where [
+                $($decl)* $From [$($Phantom)?] [$($map)?]
+                [$(
+                    $(#[$($ma)*])* $Marker $(($($marg)*))*
+                    $($(#[$($mas)*])* $Markers $(($($margs)*))*)*
+                )*]
+            ] [] [] [struct] []
+            ; $($rest)*
+        }
+    };
+    (@from [ $($decl:tt)* ] $From:ty $(, PhantomData<$Phantom:ty>)? $(=> $map:path)?
+     $(: $(#[$($ma:tt)*])* $Marker:ident $(($($marg:tt)*))?
+         $(+ $(#[$($mas:tt)*])* $Markers:ident $(($($margs:tt)*))?)*)?) => {
+        $crate::pub_iterator_type! {
+            @where [
+                $($decl)* $From [$($Phantom)?] [$($map)?]
+                [$(
+                    $(#[$($ma)*])* $Marker $(($($marg)*))*
+                    $($(#[$($mas)*])* $Markers $(($($margs)*))*)*
+                )*]
+            ] [] [] [struct] []
+        }
+    };
+    (@from [ $($decl:tt)* ] $From:ty $(, PhantomData<$Phantom:ty>)? $(=> $map:path)?
+     : $($markers:tt)*) => {
+        $crate::pub_iterator_type! { @bad_markers $($markers)* }
+    };
+    // Finds what is wrong in the markers that the arms above didn't
+    // accept, pointing at the token following a marker if it isn't a
+    // `+`.
+    (@bad_markers $(#[$($a:tt)*])* $Marker:ident $(($($arg:tt)*))? + $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @bad_markers $($rest)* }
+    };
+    (@bad_markers $(#[$($a:tt)*])* $Marker:ident $(($($arg:tt)*))? $t:tt $($rest:tt)*) => {
+        $crate::__core::compile_error!("markers are separated by `+`");
+        $crate::__pub_iterator_type_marker_separator! { $t }
+    };
+    (@bad_markers $($rest:tt)*) => {
+        $crate::__core::compile_error!("expected a marker after `:` or `+`");
+    };
+    // The where clauses are split in sections, `[struct]` (the
+    // default) applying to the struct and the impls, and `[impl]`
+    // only to the impls. Each section ends by switching to the next
+    // one, the hidden `[@end]` section ending them all.
+    (@where $decl:tt [] [] [struct] [] [struct] $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @where $decl [] [] [struct] [] $($rest)* }
+    };
+    (@where $decl:tt [] [] [struct] [] [impl] $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @where $decl [] [] [impl] [] $($rest)* }
+    };
+    (@where $decl:tt $sw:tt $iw:tt $section:tt $cur:tt , where [ $($next:tt)* ] $($rest:tt)*) => {
+        $crate::pub_iterator_type! {
+            @where $decl $sw $iw $section $cur where [$($next)*] $($rest)*
+        }
+    };
+    (@where $decl:tt $sw:tt $iw:tt $section:tt [] where [ $($next:tt)* ] $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @where $decl $sw $iw [$($next)*] [] $($rest)* }
+    };
+    (@where $decl:tt [ $($sw:tt)* ] $iw:tt [struct] [ $($cur:tt)* ]
+     where [ $($next:tt)* ] $($rest:tt)*) => {
+        $crate::pub_iterator_type! {
+            @where $decl [$($sw)* $($cur)* ,] $iw [$($next)*] [] $($rest)*
+        }
+    };
+    (@where $decl:tt $sw:tt [ $($iw:tt)* ] [impl] [ $($cur:tt)* ]
+     where [ $($next:tt)* ] $($rest:tt)*) => {
+        $crate::pub_iterator_type! {
+            @where $decl $sw [$($iw)* $($cur)* ,] [$($next)*] [] $($rest)*
+        }
+    };
+    (@where $decl:tt [ $($sw:tt)* ] [ $($iw:tt)* ] [@end] [] { $($helpers:tt)* } $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @emit $decl [$($sw)*] [$($iw)*] [$($helpers)*] }
+        $crate::pub_iterator_type! { $($rest)* }
+    };
+    (@where $decl:tt [ $($sw:tt)* ] [ $($iw:tt)* ] [@end] [] $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @emit $decl [$($sw)*] [$($iw)*] [] }
+        $crate::pub_iterator_type! { $($rest)* }
+    };
+    (@where $decl:tt $sw:tt $iw:tt $section:tt $cur:tt $(,)? { $($helpers:tt)* } $($rest:tt)*) => {
+        $crate::pub_iterator_type! {
+            @where $decl $sw $iw $section $cur where [@end] { $($helpers)* } $($rest)*
+        }
+    };
+    (@where $decl:tt $sw:tt $iw:tt $section:tt $cur:tt $(,)? ; $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @where $decl $sw $iw $section $cur where [@end] ; $($rest)* }
+    };
+    (@where $decl:tt $sw:tt $iw:tt $section:tt $cur:tt $(,)?) => {
+        $crate::pub_iterator_type! { @where $decl $sw $iw $section $cur where [@end] }
+    };
+    (@where $decl:tt $sw:tt $iw:tt $section:tt [ $($cur:tt)* ] $t:tt $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @where $decl $sw $iw $section [$($cur)* $t] $($rest)* }
+    };
+    (@emit [
+        [ $($attr:tt)* ] [ $($cfg:tt)* ] [ $($vis:tt)* ] $Name:ident
+        [ $($Param:tt)* ] [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+        [ $($Item:ty)? ] [ $($field_attr:tt)* ] [ $($field_cfg:tt)* ]
+        $From:ty [ $($Phantom:ty)? ] [ $($map:path)? ] [ $($Marker:tt)* ]
+    ] [ $($sw:tt)* ] [ $($iw:tt)* ] [ $($helpers:tt)* ]) => {
+        $($attr)*
+        $($vis)* struct $Name < $($Param)* > (
+            $($field_attr)* $From $(, $crate::__core::marker::PhantomData<$Phantom>)?
+        ) where $($sw)* ;
+        $crate::pub_iterator_type! {
+            @iterator [$($Marker)*] [] []
+            [[$($cfg)* $($field_cfg)*] $Name [$($ImplParam)*] [$($NameArg)*]
+             [$($Item)? $(=> $map)?] [$($sw)* $($iw)*] $From [$($Marker)*]]
+        }
+        $crate::pub_iterator_type! {
+            @markers [$($Marker)*] [$($map)?] [$($Marker)*]
+            [[$($cfg)* $($field_cfg)*] $Name [$($ImplParam)*] [$($NameArg)*] [$($sw)* $($iw)*]
+             $From [$($Phantom)?]]
+        }
+        $crate::pub_iterator_type! {
+            @helpers [$($Item)? $(=> $map)?]
+            [[$($cfg)* $($field_cfg)*] $Name [$($ImplParam)*] [$($NameArg)*] [$($sw)* $($iw)*]
+             $From [$($Phantom)?]]
+            $($helpers)*
+        }
+    };
+    (@one) => {};
+    (@one $(#[$($attr:tt)*])* $(pub $(($($v:tt)*))?)? mod $module:ident { $($decls:tt)* }
+     $($rest:tt)*) => {
+        $(#[$($attr)*])*
+        mod $module {
+            #[allow(unused_imports)]
+            use super::*;
+            $crate::pub_iterator_type! { $($decls)* }
+        }
+        $(pub $(($($v)*))*)* use self::$module::*;
+        $crate::pub_iterator_type! { $($rest)* }
+    };
+    (@one #[$($attr:tt)*] $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @attrs [] [] [] #[$($attr)*] $($rest)* }
+    };
+    (@one $Name:ident $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @attrs [] [] [] $Name $($rest)* }
+    };
+    // The declarations are split on the top level `;` before being
+    // parsed, each one being expanded apart from the others, so that
+    // the recursion depth doesn't add up along the list. The tokens
+    // are taken 16 by 16, the `;` being looked for in each chunk first.
+    (@split [ $($decl:tt)* ] ; $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @one $($decl)* }
+        $crate::pub_iterator_type! { @split [] $($rest)* }
+    };
+    (@split [ $($decl:tt)* ] $a:tt ; $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @one $($decl)* $a }
+        $crate::pub_iterator_type! { @split [] $($rest)* }
+    };
+    (@split [ $($decl:tt)* ] $a:tt $b:tt ; $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @one $($decl)* $a $b }
+        $crate::pub_iterator_type! { @split [] $($rest)* }
+    };
+    (@split [ $($decl:tt)* ] $a:tt $b:tt $c:tt ; $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @one $($decl)* $a $b $c }
+        $crate::pub_iterator_type! { @split [] $($rest)* }
+    };
+    (@split [ $($decl:tt)* ] $a:tt $b:tt $c:tt $d:tt ; $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @one $($decl)* $a $b $c $d }
+        $crate::pub_iterator_type! { @split [] $($rest)* }
+    };
+    (@split [ $($decl:tt)* ] $a:tt $b:tt $c:tt $d:tt $e:tt ; $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @one $($decl)* $a $b $c $d $e }
+        $crate::pub_iterator_type! { @split [] $($rest)* }
+    };
+    (@split [ $($decl:tt)* ] $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt ; $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @one $($decl)* $a $b $c $d $e $f }
+        $crate::pub_iterator_type! { @split [] $($rest)* }
+    };
+    (@split [ $($decl:tt)* ] $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt $g:tt ; $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @one $($decl)* $a $b $c $d $e $f $g }
+        $crate::pub_iterator_type! { @split [] $($rest)* }
+    };
+    (@split [ $($decl:tt)* ] $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt $g:tt $h:tt ; $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @one $($decl)* $a $b $c $d $e $f $g $h }
+        $crate::pub_iterator_type! { @split [] $($rest)* }
+    };
+    (@split [ $($decl:tt)* ]
+     $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt $g:tt $h:tt $i:tt ; $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @one $($decl)* $a $b $c $d $e $f $g $h $i }
+        $crate::pub_iterator_type! { @split [] $($rest)* }
+    };
+    (@split [ $($decl:tt)* ]
+     $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt $g:tt $h:tt $i:tt $j:tt ; $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @one $($decl)* $a $b $c $d $e $f $g $h $i $j }
+        $crate::pub_iterator_type! { @split [] $($rest)* }
+    };
+    (@split [ $($decl:tt)* ]
+     $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt $g:tt $h:tt $i:tt $j:tt $k:tt ; $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @one $($decl)* $a $b $c $d $e $f $g $h $i $j $k }
+        $crate::pub_iterator_type! { @split [] $($rest)* }
+    };
+    (@split [ $($decl:tt)* ]
+     $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt $g:tt $h:tt $i:tt $j:tt $k:tt $l:tt ; $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @one $($decl)* $a $b $c $d $e $f $g $h $i $j $k $l }
+        $crate::pub_iterator_type! { @split [] $($rest)* }
+    };
+    (@split [ $($decl:tt)* ]
+     $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt $g:tt $h:tt
+     $i:tt $j:tt $k:tt $l:tt $m:tt ; $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @one $($decl)* $a $b $c $d $e $f $g $h $i $j $k $l $m }
+        $crate::pub_iterator_type! { @split [] $($rest)* }
+    };
+    (@split [ $($decl:tt)* ]
+     $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt $g:tt $h:tt
+     $i:tt $j:tt $k:tt $l:tt $m:tt $n:tt ; $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @one $($decl)* $a $b $c $d $e $f $g $h $i $j $k $l $m $n }
+        $crate::pub_iterator_type! { @split [] $($rest)* }
+    };
+    (@split [ $($decl:tt)* ]
+     $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt $g:tt $h:tt
+     $i:tt $j:tt $k:tt $l:tt $m:tt $n:tt $o:tt ; $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @one $($decl)* $a $b $c $d $e $f $g $h $i $j $k $l $m $n $o }
+        $crate::pub_iterator_type! { @split [] $($rest)* }
+    };
+    (@split [ $($decl:tt)* ]
+     $a:tt $b:tt $c:tt $d:tt $e:tt $f:tt $g:tt $h:tt
+     $i:tt $j:tt $k:tt $l:tt $m:tt $n:tt $o:tt $p:tt $($rest:tt)*) => {
+        $crate::pub_iterator_type! {
+            @split [$($decl)* $a $b $c $d $e $f $g $h $i $j $k $l $m $n $o $p] $($rest)*
+        }
+    };
+    (@split []) => {};
+    (@split [ $($decl:tt)* ] $($rest:tt)*) => {
+        $crate::pub_iterator_type! { @one $($decl)* $($rest)* }
+    };
+    // An internal rule matching nothing above, that would else be
+    // split again as a declaration until the recursion limit.
+    (@ $($t:tt)*) => {
+        $crate::__core::compile_error!("invalid `pub_iterator_type!` declaration");
+    };
+    () => {};
+    ($($t:tt)+) => {
+        $crate::pub_iterator_type! { @split [] $($t)+ }
+    };
+}
+
+/// Declare a type hiding a boxed iterator, the hidden iterator
+/// can thus be chosen at runtime.
+///
+/// The syntax is the same as `pub_iterator_type!`, except that the
+/// hidden type is replaced by the type of the items after `->`. The
+/// first generic parameter, if it is a lifetime, is the lifetime of
+/// the boxed iterator, else it is `'static`. The generated type has a
+/// `new` constructor accepting any iterator of these items. Only
+/// available with the `alloc` feature, enabled by default.
+///
+/// # Example
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+///
+/// pub_boxed_iterator_type! {
+///     #[doc="The elements of a slice, in any order."]
+///     Dynamic['a, T] -> &'a T
+/// }
+/// pub fn iter<T>(slice: &[T], rev: bool) -> Dynamic<T> {
+///     if rev {
+///         Dynamic::new(slice.iter().rev())
+///     } else {
+///         Dynamic::new(slice.iter())
+///     }
+/// }
+///
+/// pub_boxed_iterator_type! {
+///     #[doc="Some numbers."]
+///     Numbers[] -> u32;
+///     #[doc="Some chars."]
+///     pub(crate) Chars[] -> char;
+/// }
+///
+/// # fn main() {
+/// assert_eq!(vec![&1, &2], iter(&[1, 2], false).collect::<Vec<_>>());
+/// assert_eq!(vec![&2, &1], iter(&[1, 2], true).collect::<Vec<_>>());
+/// let mut numbers = Numbers::new(0..3);
+/// assert_eq!(Some(0), numbers.next());
+/// numbers = Numbers::new(vec![4, 5].into_iter());
+/// assert_eq!(Some(4), numbers.next());
+/// assert_eq!(vec!['a', 'b'], Chars::new("ab".chars()).collect::<Vec<_>>());
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! pub_boxed_iterator_type {
+    (@head [ $($head:tt)* ]
+     $Name:ident [ $lt:lifetime $($params:tt)* ] -> $Item:ty $(; $($rest:tt)*)?) => {
+        $crate::pub_iterator_type! {
+            $($head)* $Name[$lt $($params)*] -> $Item = $crate::__alloc::boxed::Box<
+                dyn $crate::__core::iter::Iterator<Item = $Item> + $lt
+            > {
+                pub fn __new_boxed($Item, $lt);
+            }
+        }
+        $crate::pub_boxed_iterator_type! { $($($rest)*)* }
+    };
+    (@head [ $($head:tt)* ]
+     $Name:ident [ $($params:tt)* ] -> $Item:ty $(; $($rest:tt)*)?) => {
+        $crate::pub_iterator_type! {
+            $($head)* $Name[$($params)*] -> $Item = $crate::__alloc::boxed::Box<
+                dyn $crate::__core::iter::Iterator<Item = $Item>
+            > {
+                pub fn __new_boxed($Item, 'static);
+            }
+        }
+        $crate::pub_boxed_iterator_type! { $($($rest)*)* }
+    };
+    (@head [ $($head:tt)* ] $t:tt $($rest:tt)*) => {
+        $crate::pub_boxed_iterator_type! { @head [$($head)* $t] $($rest)* }
+    };
+    () => {};
+    ($($t:tt)+) => {
+        $crate::pub_boxed_iterator_type! { @head [] $($t)+ }
+    };
+}
+
+/// A generic wrapper around an iterator, as an alternative to
+/// `pub_iterator_type!`.
+///
+/// Contrary to the types generated by the macro, the wrapped type is
+/// part of the type, and is thus not hidden in the signatures. It is
+/// still useful to quickly wrap an iterator, for example to return
+/// it as an `impl Iterator`, without writing a macro invocation.
+/// `Clone` and `Debug` are implemented if the wrapped iterator
+/// implements them.
+///
+/// # Example
+///
+/// ```
+/// use pub_iterator_type::IterWrapper;
+///
+/// fn evens() -> impl Iterator<Item = u32> + Clone {
+///     IterWrapper::new((0..10).filter(|i| i % 2 == 0))
+/// }
+///
+/// let iter = evens();
+/// assert_eq!(vec![0, 2, 4, 6, 8], iter.clone().collect::<Vec<_>>());
+/// assert_eq!((0, Some(10)), iter.size_hint());
+///
+/// let mut wrapper = IterWrapper::new(vec![1, 2, 3].into_iter());
+/// assert_eq!(Some(1), wrapper.next());
+/// assert_eq!("IterWrapper(IntoIter([2, 3]))", format!("{:?}", wrapper));
+/// assert_eq!(vec![2, 3], wrapper.into_inner().collect::<Vec<_>>());
+/// ```
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IterWrapper<I>(I);
+
+impl<I> IterWrapper<I> {
+    /// Wraps an iterator.
+    #[inline]
+    pub fn new(iter: I) -> IterWrapper<I> {
+        IterWrapper(iter)
+    }
+    /// Returns the wrapped iterator.
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.0
+    }
+}
+
+impl<I: Iterator> Iterator for IterWrapper<I> {
+    type Item = I::Item;
+    #[inline]
+    fn next(&mut self) -> Option<I::Item> {
+        self.0.next()
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+/// An iterator over an owned value, as a `String` or a `Vec`, to be
+/// hidden by `pub_iterator_type!`.
+///
+/// An iterator borrowing a value can't be stored next to the value,
+/// so the owned value is stored with a position instead. At each
+/// step, the `step` function gets the rest of the value from the
+/// position, and returns the next item with the length it used, the
+/// next step starting after it. The length must not be greater than
+/// the length of the rest, and should be positive, or the iteration
+/// never ends.
+///
+/// # Example
+///
+/// ```
+/// #[macro_use] extern crate pub_iterator_type;
+/// use pub_iterator_type::Resliced;
+///
+/// pub_iterator_type! {
+///     #[doc="The lines of an owned string."]
+///     OwnedLines[] = Resliced<String, String>
+/// }
+///
+/// fn next_line(rest: &str) -> Option<(String, usize)> {
+///     let line = rest.lines().next()?;
+///     let len = rest.find('\n').map_or(rest.len(), |i| i + 1);
+///     Some((line.to_string(), len))
+/// }
+///
+/// pub fn lines(s: String) -> OwnedLines {
+///     OwnedLines(Resliced::new(s, next_line))
+/// }
+///
+/// # fn main() {
+/// let text = String::from("first\nsecond\r\n\nlast");
+/// assert_eq!(vec!["first", "second", "", "last"], lines(text).collect::<Vec<_>>());
+/// assert_eq!(0, lines(String::new()).count());
+/// # }
+/// ```
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Resliced<O, T>
+where
+    O: Index<RangeFrom<usize>>,
+{
+    owner: O,
+    position: usize,
+    step: fn(&O::Output) -> Option<(T, usize)>,
+}
+
+impl<O, T> Resliced<O, T>
+where
+    O: Index<RangeFrom<usize>>,
+{
+    /// Iterates over `owner`, calling `step` on its rest at each
+    /// iteration.
+    #[inline]
+    pub fn new(owner: O, step: fn(&O::Output) -> Option<(T, usize)>) -> Resliced<O, T> {
+        Resliced {
+            owner,
+            position: 0,
+            step,
+        }
+    }
+    /// Returns the owned value.
+    #[inline]
+    pub fn into_owner(self) -> O {
+        self.owner
+    }
+}
+
+impl<O, T> Iterator for Resliced<O, T>
+where
+    O: Index<RangeFrom<usize>>,
+{
+    type Item = T;
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        let (item, len) = (self.step)(&self.owner[self.position..])?;
+        self.position += len;
+        Some(item)
+    }
+}
+
+impl<O, T> Clone for Resliced<O, T>
+where
+    O: Index<RangeFrom<usize>> + Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Resliced {
+            owner: self.owner.clone(),
+            position: self.position,
+            step: self.step,
+        }
+    }
+}
+
+impl<O, T> fmt::Debug for Resliced<O, T>
+where
+    O: Index<RangeFrom<usize>> + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Resliced")
+            .field("owner", &self.owner)
+            .field("position", &self.position)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "exact_size_is_empty")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pub_iterator_type_if_exact_size_is_empty {
+    (@marker) => {};
+    ($($t:tt)*) => { $($t)* };
+}
+#[cfg(not(feature = "exact_size_is_empty"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pub_iterator_type_if_exact_size_is_empty {
+    (@marker) => {
+        $crate::__core::compile_error!(
+            "the `IsEmpty` marker needs the `exact_size_is_empty` feature of pub-iterator-type"
+        );
+    };
+    ($($t:tt)*) => {};
+}
+
+#[cfg(feature = "try_trait_v2")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pub_iterator_type_if_try_trait_v2 {
+    (@marker) => {};
+    ($($t:tt)*) => { $($t)* };
+}
+#[cfg(not(feature = "try_trait_v2"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pub_iterator_type_if_try_trait_v2 {
+    (@marker) => {
+        $crate::__core::compile_error!(
+            "the `TryFold` marker needs the `try_trait_v2` feature of pub-iterator-type"
+        );
+    };
+    ($($t:tt)*) => {};
+}
+
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pub_iterator_type_into_boxed {
+    ([$(#[$($fa:tt)*])* $v:vis]
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($phantom:tt)* ]]) => {
+        $($cfg)*
+        impl< $($ImplParam)* > $Name < $($NameArg)* > where $($w)* {
+            /// Boxes the iterator, hiding its type.
+            #[inline]
+            $(#[$($fa)*])*
+            $v fn into_boxed<'__a>(
+                self,
+            ) -> $crate::__alloc::boxed::Box<
+                dyn $crate::__core::iter::Iterator<
+                    Item = <Self as $crate::__core::iter::Iterator>::Item
+                > + '__a
+            >
+            where Self: '__a {
+                $crate::__alloc::boxed::Box::new(self)
+            }
+        }
+    };
+}
+#[cfg(not(feature = "alloc"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pub_iterator_type_into_boxed {
+    ($v:tt $ctx:tt) => {
+        $crate::__core::compile_error!(
+            "the `into_boxed` method needs the `alloc` feature of pub-iterator-type"
+        );
+    };
+}
+
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pub_iterator_type_collect_result {
+    ([$(#[$($fa:tt)*])* $v:vis]
+     [[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($phantom:tt)* ]]) => {
+        $($cfg)*
+        impl< $($ImplParam)* > $Name < $($NameArg)* > where $($w)* {
+            /// Collects the items, stopping at the first error.
+            #[inline]
+            $(#[$($fa)*])*
+            $v fn collect_result<__T, __E>(
+                self,
+            ) -> $crate::__core::result::Result<$crate::__alloc::vec::Vec<__T>, __E>
+            where Self: $crate::__core::iter::Iterator<
+                Item = $crate::__core::result::Result<__T, __E>
+            > {
+                $crate::__core::iter::Iterator::collect(self)
+            }
+        }
+    };
+}
+#[cfg(not(feature = "alloc"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pub_iterator_type_collect_result {
+    ($v:tt $ctx:tt) => {
+        $crate::__core::compile_error!(
+            "the `collect_result` method needs the `alloc` feature of pub-iterator-type"
+        );
+    };
+}
+
+#[cfg(feature = "advance_by")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pub_iterator_type_if_advance_by {
+    (@marker) => {};
+    ($($t:tt)*) => { $($t)* };
+}
+#[cfg(not(feature = "advance_by"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pub_iterator_type_if_advance_by {
+    (@marker) => {
+        $crate::__core::compile_error!(
+            "the `AdvanceBy` marker needs the `advance_by` feature of pub-iterator-type"
+        );
+    };
+    ($($t:tt)*) => {};
+}
+
+#[cfg(feature = "trusted_len")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pub_iterator_type_trusted_len {
+    ([[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($phantom:tt)* ]]) => {
+        $($cfg)*
+        unsafe impl< $($ImplParam)* > $crate::__core::iter::TrustedLen for $Name < $($NameArg)* >
+        where $From: $crate::__core::iter::TrustedLen, $($w)* {}
+    };
+}
+#[cfg(not(feature = "trusted_len"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pub_iterator_type_trusted_len {
+    ($ctx:tt) => {
+        $crate::__core::compile_error!(
+            "the `TrustedLen` marker needs the `trusted_len` feature of pub-iterator-type"
+        );
+    };
+}
+
+#[cfg(feature = "stream")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pub_iterator_type_stream {
+    ([[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($phantom:tt)* ]]) => {
+        $($cfg)*
+        impl< $($ImplParam)* > $crate::__futures_core::Stream for $Name < $($NameArg)* >
+        where $($w)* {
+            type Item = <Self as $crate::__core::iter::Iterator>::Item;
+            #[inline]
+            fn poll_next(
+                self: $crate::__core::pin::Pin<&mut Self>,
+                _cx: &mut $crate::__core::task::Context,
+            ) -> $crate::__core::task::Poll<$crate::__core::option::Option<Self::Item>> {
+                // SAFETY: the hidden iterator is never structurally
+                // pinned: the macro only uses it through `&mut`, as any
+                // iterator, and never gives a `Pin<&mut _>` to it. The
+                // documentation of the marker requires the same from
+                // the code around the generated type.
+                let this = unsafe { $crate::__core::pin::Pin::get_unchecked_mut(self) };
+                $crate::__core::task::Poll::Ready($crate::__core::iter::Iterator::next(&mut this.0))
+            }
+            #[inline]
+            fn size_hint(&self) -> (usize, $crate::__core::option::Option<usize>) {
+                $crate::__core::iter::Iterator::size_hint(&self.0)
+            }
+        }
+    };
+}
+#[cfg(not(feature = "stream"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pub_iterator_type_stream {
+    ($ctx:tt) => {
+        $crate::__core::compile_error!(
+            "the `Stream` marker needs the `stream` feature of pub-iterator-type"
+        );
+    };
+}
+
+#[cfg(feature = "rayon")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pub_iterator_type_rayon {
+    ([[ $($cfg:tt)* ] $Name:ident [ $($ImplParam:tt)* ] [ $($NameArg:tt)* ]
+      [ $($w:tt)* ] $From:ty [ $($phantom:tt)* ]]) => {
+        $($cfg)*
+        impl< $($ImplParam)* > $crate::__rayon::iter::ParallelIterator for $Name < $($NameArg)* >
+        where $($w)* {
+            type Item = <$From as $crate::__rayon::iter::ParallelIterator>::Item;
+            #[inline]
+            fn drive_unindexed<__C>(self, consumer: __C) -> __C::Result
+            where __C: $crate::__rayon::iter::plumbing::UnindexedConsumer<Self::Item> {
+                $crate::__rayon::iter::ParallelIterator::drive_unindexed(self.0, consumer)
+            }
+            #[inline]
+            fn opt_len(&self) -> $crate::__core::option::Option<usize> {
+                $crate::__rayon::iter::ParallelIterator::opt_len(&self.0)
+            }
+        }
+    };
+}
+#[cfg(not(feature = "rayon"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pub_iterator_type_rayon {
+    ($ctx:tt) => {
+        $crate::__core::compile_error!(
+            "the `ParallelIterator` marker needs the `rayon` feature of pub-iterator-type"
+        );
+    };
+}
+
+// Only accepts `+`, to point at the wrong marker separator.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pub_iterator_type_marker_separator {
+    (+) => {};
 }
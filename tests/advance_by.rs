@@ -0,0 +1,67 @@
+#![cfg(feature = "advance_by")]
+#![feature(iter_advance_by)]
+
+#[macro_use]
+extern crate pub_iterator_type;
+
+use std::num::NonZeroUsize;
+
+pub_iterator_type! {
+    #[doc="A lot of numbers."]
+    Numbers[] = std::ops::Range<u64> : DoubleEndedIterator + AdvanceBy;
+    #[doc="The elements of a slice."]
+    Iter['a, T] = std::slice::Iter<'a, T>
+        : DoubleEndedIterator + ExactSizeIterator + AdvanceBy;
+}
+
+#[test]
+fn advance_by() {
+    let mut numbers = Numbers(0..u64::max_value());
+    assert_eq!(Ok(()), numbers.advance_by(1_000_000_000_000));
+    assert_eq!(Some(1_000_000_000_000), numbers.next());
+    let mut numbers = Numbers(0..3);
+    assert_eq!(Err(NonZeroUsize::new(2).unwrap()), numbers.advance_by(5));
+}
+
+#[test]
+fn advance_back_by() {
+    let mut numbers = Numbers(0..u64::max_value());
+    assert_eq!(Ok(()), numbers.advance_back_by(1_000_000_000_000));
+    assert_eq!(
+        Some(u64::max_value() - 1_000_000_000_001),
+        numbers.next_back()
+    );
+}
+
+#[test]
+fn both_ends() {
+    let v: Vec<u32> = (0..1_000_000).collect();
+    let mut iter = Iter(v.iter());
+    assert_eq!(Ok(()), iter.advance_by(10));
+    assert_eq!(Ok(()), iter.advance_back_by(10));
+    assert_eq!(999_980, iter.len());
+    assert_eq!(Some(&10), iter.next());
+    assert_eq!(Some(&999_989), iter.next_back());
+    assert_eq!(Some(&20), iter.nth(9));
+    assert_eq!(Some(&999_978), iter.nth_back(10));
+    assert_eq!(
+        Err(NonZeroUsize::new(42).unwrap()),
+        iter.advance_back_by(iter.len() + 42)
+    );
+}
+
+#[test]
+fn nth() {
+    let mut numbers = Numbers(0..u64::max_value());
+    assert_eq!(Some(1_000_000_000_000), numbers.nth(1_000_000_000_000));
+    assert_eq!(Some(1_000_000_000_001), numbers.next());
+}
+
+#[test]
+fn skip() {
+    let v: Vec<u32> = (0..1_000_000).collect();
+    let mut skipped = Iter(v.iter()).skip(999_998);
+    assert_eq!(Some(&999_998), skipped.next());
+    assert_eq!(Some(&999_999), skipped.next());
+    assert_eq!(None, skipped.next());
+}
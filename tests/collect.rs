@@ -0,0 +1,94 @@
+#[macro_use]
+extern crate pub_iterator_type;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// The system allocator, counting the allocations of each thread.
+struct Counting;
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|a| a.set(a.get() + 1));
+        System.alloc(layout)
+    }
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|a| a.set(a.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// The result of `f` and the number of allocations it did.
+fn allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+pub_iterator_type! {
+    #[doc="Some numbers."]
+    Numbers[] = std::ops::Range<u32>;
+    #[doc="Some chars."]
+    Chars['a] = std::str::Chars<'a>;
+    #[doc="Some chars, counting the calls to `fold`."]
+    Folded['a] = CountFolds<'a>;
+}
+
+/// Chars, counting the calls to `fold`.
+pub struct CountFolds<'a>(std::str::Chars<'a>, &'a Cell<usize>);
+impl<'a> Iterator for CountFolds<'a> {
+    type Item = char;
+    fn next(&mut self) -> Option<char> {
+        self.0.next()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+    fn fold<B, F: FnMut(B, char) -> B>(self, init: B, f: F) -> B {
+        self.1.set(self.1.get() + 1);
+        self.0.fold(init, f)
+    }
+}
+
+#[test]
+fn preallocates() {
+    let raw = (0..1000).collect::<Vec<u32>>();
+    let wrapped = Numbers(0..1000).collect::<Vec<u32>>();
+    assert_eq!(raw, wrapped);
+    assert_eq!(raw.capacity(), wrapped.capacity());
+}
+
+#[test]
+fn same_as_raw() {
+    assert_eq!((0..1000).size_hint(), Numbers(0..1000).size_hint());
+    let (raw, raw_allocations) = allocations(|| (0..1000).collect::<Vec<u32>>());
+    let (wrapped, wrapped_allocations) = allocations(|| Numbers(0..1000).collect::<Vec<u32>>());
+    assert_eq!(raw, wrapped);
+    assert_eq!(1, raw_allocations);
+    assert_eq!(raw_allocations, wrapped_allocations);
+
+    let s = "some chars, with an inexact size hint";
+    assert_eq!(s.chars().size_hint(), Chars(s.chars()).size_hint());
+    let (raw, raw_allocations) = allocations(|| s.chars().collect::<Vec<char>>());
+    let (wrapped, wrapped_allocations) = allocations(|| Chars(s.chars()).collect::<Vec<char>>());
+    assert_eq!(raw, wrapped);
+    assert_eq!(raw_allocations, wrapped_allocations);
+}
+
+#[test]
+fn string() {
+    let s = "forwarded";
+    assert_eq!(s, Chars(s.chars()).collect::<String>());
+    let folds = Cell::new(0);
+    assert_eq!(s, Folded(CountFolds(s.chars(), &folds)).collect::<String>());
+    assert_eq!(1, folds.get());
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "alloc")]
+
+#[macro_use]
+extern crate pub_iterator_type;
+
+pub_iterator_type! {
+    #[doc="Some parsed numbers."]
+    Parsed[] -> Result<i32, String> = std::vec::IntoIter<Result<i32, String>> {
+        pub fn collect_result(self);
+    }
+}
+
+fn parse(s: &str) -> Parsed {
+    let v: Vec<_> = s
+        .split(',')
+        .map(|n| n.parse().map_err(|_| format!("bad number {:?}", n)))
+        .collect();
+    Parsed(v.into_iter())
+}
+
+fn sum(parsed: Parsed) -> Result<i32, String> {
+    let mut sum = 0;
+    for n in parsed {
+        sum += n?;
+    }
+    Ok(sum)
+}
+
+#[test]
+fn collect_result() {
+    assert_eq!(Ok(vec![1, 2, 3]), parse("1,2,3").collect_result());
+    assert_eq!(
+        Err("bad number \"a\"".to_string()),
+        parse("1,a,b").collect_result()
+    );
+}
+
+#[test]
+fn size_hint() {
+    assert_eq!((3, Some(3)), parse("1,2,3").size_hint());
+}
+
+#[test]
+fn question_mark() {
+    assert_eq!(Ok(6), sum(parse("1,2,3")));
+    assert_eq!(Err("bad number \"\"".to_string()), sum(parse("1,,3")));
+}
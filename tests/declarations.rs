@@ -0,0 +1,100 @@
+//! Long lists of declarations, each one being expanded apart from
+//! the others.
+
+#![deny(missing_docs)]
+
+#[macro_use]
+extern crate pub_iterator_type;
+
+pub_iterator_type! {
+    #[doc="Some numbers."]
+    Numbers1[] = std::ops::Range<u32>;
+    #[doc="Some cloned values."]
+    Values2[T] = std::vec::IntoIter<T> : DoubleEndedIterator where T: Clone;
+    #[doc="Some numbers."]
+    Numbers3[] = std::ops::Range<u32>;
+    #[doc="Some cloned values."]
+    Values4[T] = std::vec::IntoIter<T> : DoubleEndedIterator where T: Clone;
+    #[doc="Some numbers."]
+    Numbers5[] = std::ops::Range<u32>;
+    #[doc="Some cloned values."]
+    Values6[T] = std::vec::IntoIter<T> : DoubleEndedIterator where T: Clone;
+    #[doc="Some numbers."]
+    Numbers7[] = std::ops::Range<u32>;
+    #[doc="Some cloned values."]
+    Values8[T] = std::vec::IntoIter<T> : DoubleEndedIterator where T: Clone;
+    #[doc="Some numbers."]
+    Numbers9[] = std::ops::Range<u32>;
+    #[doc="Some cloned values."]
+    Values10[T] = std::vec::IntoIter<T> : DoubleEndedIterator where T: Clone;
+    #[doc="Some numbers."]
+    Numbers11[] = std::ops::Range<u32>;
+    #[doc="Some cloned values."]
+    Values12[T] = std::vec::IntoIter<T> : DoubleEndedIterator where T: Clone;
+    #[doc="Some numbers."]
+    Numbers13[] = std::ops::Range<u32>;
+    #[doc="Some cloned values."]
+    Values14[T] = std::vec::IntoIter<T> : DoubleEndedIterator where T: Clone;
+    #[doc="Some numbers."]
+    Numbers15[] = std::ops::Range<u32>;
+    #[doc="Some cloned values."]
+    Values16[T] = std::vec::IntoIter<T> : DoubleEndedIterator where T: Clone;
+    #[doc="Some numbers."]
+    Numbers17[] = std::ops::Range<u32>;
+    #[doc="Some cloned values."]
+    Values18[T] = std::vec::IntoIter<T> : DoubleEndedIterator where T: Clone;
+    #[doc="Some numbers."]
+    Numbers19[] = std::ops::Range<u32>;
+    #[doc="Some cloned values."]
+    Values20[T] = std::vec::IntoIter<T> : DoubleEndedIterator where T: Clone;
+    #[doc="Some numbers."]
+    Numbers21[] = std::ops::Range<u32>;
+    #[doc="Some cloned values."]
+    Values22[T] = std::vec::IntoIter<T> : DoubleEndedIterator where T: Clone;
+    #[doc="Some numbers."]
+    Numbers23[] = std::ops::Range<u32>;
+    #[doc="Some cloned values."]
+    Values24[T] = std::vec::IntoIter<T> : DoubleEndedIterator where T: Clone;
+}
+
+#[test]
+fn first() {
+    assert_eq!(Numbers1(0..3).sum::<u32>(), 3);
+    assert_eq!(Values2(vec![1, 2].into_iter()).next_back(), Some(2));
+}
+
+#[test]
+fn last() {
+    assert_eq!(Numbers23(0..3).sum::<u32>(), 3);
+    assert_eq!(Values24(vec!['a', 'b'].into_iter()).next_back(), Some('b'));
+}
+
+pub_iterator_type! {
+    #[doc="Clones of a value, with a trailing comma in the parameters."]
+    from_fn Trailing[T: Clone,] -> T;
+    #[doc="Pairs of values, with several parameters."]
+    from_fn Pairs[A: Clone, B: Clone] -> (A, B);
+}
+
+#[test]
+fn from_fn_parameters() {
+    assert_eq!(Trailing::new(|| Some(1)).next(), Some(1));
+    assert_eq!(Pairs::new(|| Some((1, 'a'))).next(), Some((1, 'a')));
+}
+
+mod bare {
+    pub_iterator_type! {
+        #[doc="Some numbers, public without visibility."]
+        Bare[] = std::ops::Range<u32>
+    }
+    /// The numbers below `n`.
+    pub fn below(n: u32) -> Bare {
+        Bare(0..n)
+    }
+}
+
+#[test]
+fn public_without_visibility() {
+    let numbers: bare::Bare = bare::below(3);
+    assert_eq!(numbers.sum::<u32>(), 3);
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "alloc")]
+
+#[macro_use]
+extern crate pub_iterator_type;
+
+pub_iterator_type! {
+    #[doc="Some numbers."]
+    Numbers[I] = I where I: Iterator<Item = i32> {
+        pub fn into_boxed(self);
+    };
+    #[doc="Some borrowed numbers."]
+    Borrowed['a] = std::iter::Cloned<std::slice::Iter<'a, i32>> {
+        pub fn into_boxed(self);
+    };
+}
+
+#[test]
+fn into_boxed() {
+    let v: Vec<Box<dyn Iterator<Item = i32>>> = vec![
+        Numbers(0..2).into_boxed(),
+        Numbers(vec![5, 6].into_iter()).into_boxed(),
+    ];
+    let all: Vec<i32> = v.into_iter().flatten().collect();
+    assert_eq!(vec![0, 1, 5, 6], all);
+}
+
+#[test]
+fn into_boxed_borrowed() {
+    let v = [1, 2];
+    let boxed = Borrowed(v.iter().cloned()).into_boxed();
+    assert_eq!(vec![1, 2], boxed.collect::<Vec<_>>());
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "exact_size_is_empty")]
+#![feature(exact_size_is_empty)]
+
+#[macro_use]
+extern crate pub_iterator_type;
+
+use std::cell::Cell;
+
+pub_iterator_type! {
+    #[doc="Some numbers, counting the calls to `is_empty`."]
+    Numbers['a] = CountIsEmpty<'a> : ExactSizeIterator + IsEmpty;
+    #[doc="The same numbers, with an exact size hint."]
+    Exact['a] = CountIsEmpty<'a> : ExactSizeHint + IsEmpty;
+}
+
+/// Numbers, counting the calls to `is_empty`.
+pub struct CountIsEmpty<'a>(std::ops::Range<u32>, &'a Cell<usize>);
+impl<'a> Iterator for CountIsEmpty<'a> {
+    type Item = u32;
+    fn next(&mut self) -> Option<u32> {
+        self.0.next()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+impl<'a> ExactSizeIterator for CountIsEmpty<'a> {
+    fn is_empty(&self) -> bool {
+        self.1.set(self.1.get() + 1);
+        self.0.is_empty()
+    }
+}
+
+#[test]
+fn is_empty() {
+    let calls = Cell::new(0);
+    let mut numbers = Numbers(CountIsEmpty(0..1, &calls));
+    assert!(!numbers.is_empty());
+    assert_eq!(Some(0), numbers.next());
+    assert!(numbers.is_empty());
+    assert_eq!(2, calls.get());
+    let exact = Exact(CountIsEmpty(0..0, &calls));
+    assert!(exact.is_empty());
+    assert_eq!(3, calls.get());
+}
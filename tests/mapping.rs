@@ -0,0 +1,31 @@
+//! The types returned by the helpers keep the mapping of the items.
+
+#[macro_use]
+extern crate pub_iterator_type;
+
+pub_iterator_type! {
+    #[doc="Some numbers, as `i64`."]
+    Numbers[] -> i64 = std::ops::Range<u32> => wide : DoubleEndedIterator {
+        pub fn rev(self) -> RevNumbers;
+        pub fn by_ref(&mut self) -> BorrowedNumbers;
+    }
+}
+
+fn wide(n: u32) -> i64 {
+    i64::from(n) * 10
+}
+
+#[test]
+fn rev() {
+    let rev: RevNumbers = Numbers(0..3).rev();
+    assert_eq!(vec![20i64, 10, 0], rev.collect::<Vec<i64>>());
+    assert_eq!(Some(0i64), Numbers(0..3).rev().next_back());
+}
+
+#[test]
+fn by_ref() {
+    let mut numbers = Numbers(0..4);
+    let borrowed: BorrowedNumbers = numbers.by_ref();
+    assert_eq!(vec![0i64, 10], borrowed.take(2).collect::<Vec<i64>>());
+    assert_eq!(vec![20i64, 30], numbers.collect::<Vec<i64>>());
+}
@@ -0,0 +1,23 @@
+#![no_std]
+
+#[macro_use]
+extern crate pub_iterator_type;
+
+pub_iterator_type! {
+    #[doc="An iterator that yield infinitelly the default value."]
+    #[derive(Clone)]
+    RepeatDefault[T] = core::iter::Repeat<T> : Debug + FusedIterator
+    where T: Default + Clone
+}
+
+fn repeat_default<T: Default + Clone>() -> RepeatDefault<T> {
+    RepeatDefault(core::iter::repeat(T::default()))
+}
+
+#[test]
+fn repeat() {
+    let mut iter = repeat_default::<i32>();
+    assert_eq!(Some(0), iter.next());
+    assert_eq!(Some(0), iter.nth(41));
+    assert_eq!(0, iter.take(10).sum::<i32>());
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "rayon")]
+
+#[macro_use]
+extern crate pub_iterator_type;
+extern crate rayon;
+
+use rayon::prelude::*;
+
+pub_iterator_type! {
+    #[doc="Some numbers, in parallel."]
+    Numbers[] = rayon::range::Iter<u64> : ParallelIterator;
+    #[doc="The elements of a slice, in parallel."]
+    Iter['a, T] = rayon::slice::Iter<'a, T> : ParallelIterator + Debug where T: Sync;
+}
+
+#[test]
+fn sum() {
+    let numbers = Numbers((0..1000).into_par_iter());
+    assert_eq!(499_500, numbers.sum::<u64>());
+}
+
+#[test]
+fn opt_len() {
+    assert_eq!(Some(1000), Numbers((0..1000).into_par_iter()).opt_len());
+}
+
+#[test]
+fn with_where_clause() {
+    let v: Vec<u64> = (0..1000).collect();
+    let iter = Iter(v.par_iter());
+    assert_eq!("Iter { .. }", format!("{:?}", iter));
+    assert_eq!(999_000, iter.map(|i| i * 2).sum::<u64>());
+}
@@ -0,0 +1,39 @@
+//! Runs rustdoc on `tests/rustdoc`, checking that the items hidden by
+//! the declarations are not documented.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn hidden_items() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let target = root.join("target").join("rustdoc");
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned());
+    let status = Command::new(cargo)
+        .arg("doc")
+        .arg("--quiet")
+        .arg("--manifest-path")
+        .arg(root.join("tests").join("rustdoc").join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let doc = target.join("doc").join("hidden_items");
+    let numbers = fs::read_to_string(doc.join("struct.Numbers.html")).unwrap();
+    let start = numbers.find("id=\"implementations-list\"").unwrap();
+    let end = numbers.find("id=\"trait-implementations\"").unwrap();
+    let inherent = &numbers[start..end];
+    assert!(inherent.contains("method.into_inner"));
+    assert!(inherent.contains("method.enumerate"));
+    assert!(!inherent.contains("method.new"));
+    assert!(!inherent.contains("method.rev"));
+    assert!(doc.join("struct.EnumeratedNumbers.html").exists());
+    assert!(!doc.join("struct.RevNumbers.html").exists());
+    let index = fs::read_to_string(doc.join("index.html")).unwrap();
+    assert!(!index.contains("RevNumbers"));
+    assert!(!index.contains("Constants"));
+}
@@ -0,0 +1,16 @@
+//! Documented by `tests/rustdoc.rs`, checking what is hidden.
+
+#[macro_use]
+extern crate pub_iterator_type;
+
+pub_iterator_type! {
+    #[doc="Some numbers."]
+    Numbers[] = std::ops::Range<u32> : DoubleEndedIterator + Send {
+        pub fn into_inner(self);
+        #[doc(hidden)]
+        pub fn new(inner) -> Self;
+        #[doc(hidden)]
+        pub fn rev(self) -> RevNumbers;
+        pub fn enumerate(self) -> EnumeratedNumbers;
+    }
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "stream")]
+
+#[macro_use]
+extern crate pub_iterator_type;
+extern crate futures;
+
+use futures::executor::block_on;
+use futures::stream::StreamExt;
+
+pub struct NotUnpin(std::ops::Range<u32>, std::marker::PhantomPinned);
+impl Iterator for NotUnpin {
+    type Item = u32;
+    fn next(&mut self) -> Option<u32> {
+        self.0.next()
+    }
+}
+
+pub_iterator_type! {
+    #[doc="Some numbers, also as a stream."]
+    Numbers[] = std::ops::Range<u32> : Stream;
+    #[doc="Some cloned elements, also as a stream."]
+    Cloned['a, T] = std::iter::Cloned<std::slice::Iter<'a, T>> : Stream where T: Clone;
+    #[doc="Some numbers from a type that is not `Unpin`."]
+    Pinned[] = NotUnpin : Stream;
+}
+
+#[test]
+fn collect() {
+    assert_eq!(
+        vec![0, 1, 2],
+        block_on(StreamExt::collect::<Vec<_>>(Numbers(0..3)))
+    );
+}
+
+#[test]
+fn collect_with_where_clause() {
+    let v = vec![1, 2, 3];
+    let stream = Cloned(v.iter().cloned());
+    assert_eq!(v, block_on(StreamExt::collect::<Vec<_>>(stream)));
+}
+
+#[test]
+fn collect_not_unpin() {
+    let stream = Box::pin(Pinned(NotUnpin(0..3, std::marker::PhantomPinned)));
+    assert_eq!(
+        vec![0, 1, 2],
+        block_on(StreamExt::collect::<Vec<_>>(stream))
+    );
+}
+
+#[test]
+fn size_hint() {
+    assert_eq!((3, Some(3)), futures::Stream::size_hint(&Numbers(0..3)));
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "trusted_len")]
+#![feature(trusted_len)]
+
+#[macro_use]
+extern crate pub_iterator_type;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+pub_iterator_type! {
+    #[doc="Two chained ranges."]
+    Ranges[] = std::iter::Chain<std::ops::Range<u32>, std::ops::Range<u32>> : TrustedLen
+}
+
+#[test]
+fn collect_allocates_once() {
+    let iter = Ranges((0..1000).chain(2000..3000));
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    let v: Vec<u32> = iter.collect();
+    assert_eq!(1, ALLOCATIONS.load(Ordering::SeqCst) - before);
+    assert_eq!(2000, v.len());
+}
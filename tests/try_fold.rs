@@ -0,0 +1,98 @@
+#![cfg(feature = "try_trait_v2")]
+#![feature(try_trait_v2)]
+
+#[macro_use]
+extern crate pub_iterator_type;
+
+use std::cell::Cell;
+use std::ops::Try;
+
+pub_iterator_type! {
+    #[doc="Some numbers, counting the calls to `try_fold` and `try_rfold`."]
+    Numbers['a] = CountTryFolds<'a> : DoubleEndedIterator + TryFold;
+    #[doc="The same numbers, as `i64`."]
+    Wide['a] -> i64 = CountTryFolds<'a> => i64::from : DoubleEndedIterator + TryFold;
+}
+
+/// Numbers, counting the calls to `try_fold` and `try_rfold`.
+pub struct CountTryFolds<'a>(std::ops::Range<u32>, &'a Cell<usize>);
+impl<'a> Iterator for CountTryFolds<'a> {
+    type Item = u32;
+    fn next(&mut self) -> Option<u32> {
+        self.0.next()
+    }
+    fn try_fold<B, F, R>(&mut self, init: B, f: F) -> R
+    where
+        F: FnMut(B, u32) -> R,
+        R: Try<Output = B>,
+    {
+        self.1.set(self.1.get() + 1);
+        self.0.try_fold(init, f)
+    }
+}
+impl<'a> DoubleEndedIterator for CountTryFolds<'a> {
+    fn next_back(&mut self) -> Option<u32> {
+        self.0.next_back()
+    }
+    fn try_rfold<B, F, R>(&mut self, init: B, f: F) -> R
+    where
+        F: FnMut(B, u32) -> R,
+        R: Try<Output = B>,
+    {
+        self.1.set(self.1.get() + 1);
+        self.0.try_rfold(init, f)
+    }
+}
+
+#[test]
+fn try_fold() {
+    let calls = Cell::new(0);
+    let mut numbers = Numbers(CountTryFolds(0..10, &calls));
+    assert_eq!(
+        Some(45),
+        numbers.try_fold(0u32, |acc, i| acc.checked_add(i))
+    );
+    assert_eq!(1, calls.get());
+    let mut wide = Wide(CountTryFolds(0..10, &calls));
+    assert_eq!(
+        None,
+        wide.try_fold(0i64, |acc, i| if i < 5 { Some(acc + i) } else { None })
+    );
+    assert_eq!(2, calls.get());
+    assert_eq!(Some(6), wide.next());
+}
+
+#[test]
+fn try_for_each() {
+    let calls = Cell::new(0);
+    let mut numbers = Numbers(CountTryFolds(0..10, &calls));
+    assert_eq!(
+        Err(3),
+        numbers.try_for_each(|i| if i < 3 { Ok(()) } else { Err(i) })
+    );
+    assert_eq!(1, calls.get());
+    let mut wide = Wide(CountTryFolds(0..10, &calls));
+    assert_eq!(
+        Err(3i64),
+        wide.try_for_each(|i| if i < 3 { Ok(()) } else { Err(i) })
+    );
+    assert_eq!(2, calls.get());
+}
+
+#[test]
+fn try_rfold() {
+    let calls = Cell::new(0);
+    let mut numbers = Numbers(CountTryFolds(0..10, &calls));
+    assert_eq!(
+        Err(7),
+        numbers.try_rfold(0, |acc, i| if i > 7 { Ok(acc + i) } else { Err(i) })
+    );
+    assert_eq!(1, calls.get());
+    assert_eq!(Some(6), numbers.next_back());
+    let mut wide = Wide(CountTryFolds(0..10, &calls));
+    assert_eq!(
+        Some(45i64),
+        wide.try_rfold(0i64, |acc, i| acc.checked_add(i))
+    );
+    assert_eq!(2, calls.get());
+}
@@ -0,0 +1,9 @@
+#[macro_use]
+extern crate pub_iterator_type;
+
+pub_iterator_type! {
+    #[doc="Not an iterator of `u64`."]
+    Numbers[] -> u64 = std::ops::Range<u32>
+}
+
+fn main() {}
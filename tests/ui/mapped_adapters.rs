@@ -0,0 +1,18 @@
+#[macro_use]
+extern crate pub_iterator_type;
+
+pub_iterator_type! {
+    #[doc="Some numbers, as `i64`."]
+    Numbers[] -> i64 = std::ops::Range<u32> => i64::from {
+        fn chain(self, other) -> Chained;
+        fn peekable(self) -> PeekableNumbers;
+        fn enumerate(self) -> EnumeratedNumbers;
+        fn take_while(self, predicate) -> SmallNumbers;
+        fn skip_while(self, predicate) -> LargeNumbers;
+        fn flat_map(self, f) -> Flattened;
+        fn cloned(self) -> ClonedNumbers;
+        fn copied(self) -> CopiedNumbers;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+#[macro_use]
+extern crate pub_iterator_type;
+
+pub_iterator_type! {
+    #[doc="Some numbers, as `i64`."]
+    Numbers[] = std::ops::Range<u32> => i64::from
+}
+
+fn main() {}
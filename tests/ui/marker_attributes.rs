@@ -0,0 +1,12 @@
+#[macro_use]
+extern crate pub_iterator_type;
+
+pub_iterator_type! {
+    #[doc="Some even numbers."]
+    Even[] = std::iter::Filter<std::ops::Range<u32>, fn(&u32) -> bool>
+        : #[cfg(any())] ExactSizeHint + #[cfg(any())] MinMax;
+    #[doc="Some numbers."]
+    Numbers[] = std::ops::Range<u32> : #[cfg(any())] UpperBound(1);
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#![deny(missing_docs)]
+//! Missing documentation.
+
+#[macro_use]
+extern crate pub_iterator_type;
+
+pub_iterator_type! {
+    Numbers[] = std::ops::Range<u32>
+}
+
+fn main() {}
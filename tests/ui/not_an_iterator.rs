@@ -0,0 +1,11 @@
+#[macro_use]
+extern crate pub_iterator_type;
+
+pub struct NotAnIterator;
+
+pub_iterator_type! {
+    #[doc="Not an iterator."]
+    Wrapper[] = NotAnIterator
+}
+
+fn main() {}
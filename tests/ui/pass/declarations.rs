@@ -0,0 +1,22 @@
+#![deny(missing_docs)]
+//! Several valid declarations.
+
+#[macro_use]
+extern crate pub_iterator_type;
+
+pub_iterator_type! {
+    #[doc="Some numbers."]
+    Numbers[] = std::ops::Range<u32> : DoubleEndedIterator + ExactSizeIterator;
+    #[doc="An iterator that moves out of a vector."]
+    IntoIter[T] -> T = std::vec::IntoIter<T> where T: Clone {
+        pub fn into_inner(self);
+    };
+    #[doc="Some numbers, as `i64`."]
+    Wide[] -> i64 = std::ops::Range<u32> => i64::from;
+}
+
+fn main() {
+    assert_eq!(3, Numbers(0..3).len());
+    assert_eq!(vec![1], IntoIter(vec![1].into_iter()).into_inner().collect::<Vec<_>>());
+    assert_eq!(Some(0), Wide(0..3).next());
+}
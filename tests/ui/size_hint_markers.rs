@@ -0,0 +1,13 @@
+#[macro_use]
+extern crate pub_iterator_type;
+
+pub_iterator_type! {
+    #[doc="Some numbers."]
+    Bounded[] = std::ops::Range<u32> : UpperBound(3) + UpperBound(5);
+    #[doc="Some numbers."]
+    Both[] = std::ops::Range<u32> : ExactSizeHint + UpperBound(5);
+    #[doc="Some numbers."]
+    Exact[] = std::ops::Range<u32> : ExactSizeHint + ExactSizeIterator;
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#[macro_use]
+extern crate pub_iterator_type;
+
+pub_iterator_type! {
+    #[doc="Some numbers."]
+    Numbers[] = std::ops::Range<u32> {
+        fn frobnicate(self);
+    }
+}
+
+fn main() {}
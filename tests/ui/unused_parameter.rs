@@ -0,0 +1,9 @@
+#[macro_use]
+extern crate pub_iterator_type;
+
+pub_iterator_type! {
+    #[doc="An iterator that moves out of a vector."]
+    IntoIter[T, U] = std::vec::IntoIter<T>
+}
+
+fn main() {}